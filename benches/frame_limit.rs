@@ -0,0 +1,61 @@
+use std::time::{Duration, Instant};
+
+use bewegrs::counter::Counter;
+use bewegrs::sfml::system::{Clock, Time, sleep};
+use criterion::{Criterion, criterion_group, criterion_main};
+
+/// Frame rate both limiters aim for
+const FPS: u64 = 120;
+
+/// Runs `iters` frames and sums up how far each of them is off the target frame time
+///
+/// Criterion divides this by the frames, so the reported time is the mean error per frame. The
+/// lower it is, the steadier the frame times are.
+fn pacing_error(iters: u64, mut end_frame: impl FnMut()) -> Duration {
+    let target = Duration::from_secs_f64(1.0 / FPS as f64);
+    let mut error = Duration::ZERO;
+    let mut last = Instant::now();
+    for _ in 0..iters {
+        end_frame();
+        let now = Instant::now();
+        error += (now - last).abs_diff(target);
+        last = now;
+    }
+    error
+}
+
+fn bench_frame_limit(c: &mut Criterion) {
+    let mut group = c.benchmark_group("frame_limit");
+    group.sample_size(20);
+
+    // sleeps most of the frame and busy waits for the rest
+    let mut counter = Counter::start(FPS).unwrap();
+    counter.frame_start();
+    group.bench_function("limit_frame", |b| {
+        b.iter_custom(|iters| {
+            pacing_error(iters, || {
+                counter.limit_frame();
+                counter.frame_start();
+            })
+        })
+    });
+
+    // what the limit of a SFML window does in display: sleep for the rest of the frame
+    let mut clock = Clock::start().unwrap();
+    let frame_time = 1.0 / FPS as f32;
+    group.bench_function("sfml_limit", |b| {
+        b.iter_custom(|iters| {
+            pacing_error(iters, || {
+                sleep(Time::seconds(
+                    frame_time - clock.elapsed_time().as_seconds(),
+                ));
+                clock.restart();
+            })
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_frame_limit);
+criterion_main!(benches);
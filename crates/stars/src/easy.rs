@@ -20,8 +20,9 @@ use sfml::{
 use tracing::{debug, error, info};
 
 use bewegrs::{
+    best_video_mode,
     counter::Counter,
-    graphic::{ComprehensiveElement, ComprehensiveUi, elements::info::Info},
+    graphic::{ComprehensiveElement, ComprehensiveUi, elements::info::Info, projection},
     setup,
 };
 
@@ -90,7 +91,7 @@ pub fn stars(args: Vec<String>) -> BwgResult<()> {
         .expect("could not get fps option");
     info!("exit_after: {exit_after:?}");
 
-    let video = VideoMode::fullscreen_modes()[0];
+    let video = best_video_mode()?;
     info!("video mode: {video:?}");
     let mut window = RenderWindow::new(
         video,
@@ -219,7 +220,6 @@ struct StarRenderCtx<'render> {
     index: usize,
     texture_size: &'render Vector2u,
     color: &'render Color,
-    aspect_ratio: f32,
 }
 
 impl Star {
@@ -304,15 +304,21 @@ impl Star {
 
         // If star is active, create a visible quad
         // Calculate perspective scale factor
-        let scale = NEAR_PLANE / self.distance;
+        let scale = projection::scale(self.distance, NEAR_PLANE);
 
         // Calculate projected screen position
-        let screen_x = self.position.x * scale * ctx.aspect_ratio + ctx.width as f32 / 2.0;
-        let screen_y = self.position.y * scale + ctx.height as f32 / 2.0;
+        let Vector2f {
+            x: screen_x,
+            y: screen_y,
+        } = projection::project(
+            self.position,
+            self.distance,
+            NEAR_PLANE,
+            Vector2u::new(ctx.width, ctx.height),
+        );
 
-        // Depth ratio for color (farther stars are dimmer)
-        let depth_ratio = (self.distance - NEAR_PLANE) / (FAR_PLANE - NEAR_PLANE);
-        let brightness = ((1.0 - depth_ratio) * 255.0) as u8;
+        // farther stars are dimmer
+        let brightness = projection::brightness(self.distance, NEAR_PLANE, FAR_PLANE);
 
         // Calculate radius based on distance
         let radius = STAR_RADIUS * scale;
@@ -413,8 +419,6 @@ impl Stars {
     }
 
     fn update_vertices(&mut self) -> SfResult<()> {
-        let aspect_ratio = self.video.width as f32 / self.video.height as f32;
-
         self.stars.iter().enumerate().for_each(|(i, star)| {
             let mut ctx = StarRenderCtx {
                 width: self.video.width,
@@ -423,7 +427,6 @@ impl Stars {
                 index: i,
                 texture_size: &self.texture_size,
                 color: &self.texture_color,
-                aspect_ratio,
             };
 
             star.update_vertices(&mut ctx);
@@ -434,8 +437,7 @@ impl Stars {
     }
 
     pub fn sort(&mut self, frame: u64) {
-        self.stars
-            .sort_by(|a, b| b.distance.partial_cmp(&a.distance).unwrap());
+        self.stars.sort_by(|a, b| b.distance.total_cmp(&a.distance));
         self.last_sorted_frame = frame;
     }
 
@@ -1,18 +1,33 @@
+#[cfg(feature = "serde")]
+use std::fs::File;
+#[cfg(feature = "serde")]
+use std::io::{BufReader, BufWriter};
+use std::ops::{Range, RangeInclusive};
+#[cfg(feature = "serde")]
+use std::path::Path;
 use std::path::PathBuf;
+use std::str::FromStr;
 
-use bewegrs::errors::BwgResult;
+#[cfg(feature = "serde")]
+use anyhow::Context;
+use anyhow::anyhow;
+use bewegrs::errors::{BwgError, BwgResult};
 use rayon::prelude::*;
 
+use bewegrs::egui;
 use bewegrs::sfml;
 use bewegrs::tracing;
 
 use getopts::Options;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use sfml::{
     SfResult,
     cpp::FBox,
     graphics::{
-        Color, FloatRect, Font, Image, IntRect, PrimitiveType, RectangleShape, RenderTarget,
-        RenderWindow, Texture, Transformable, Vertex, VertexBuffer, VertexBufferUsage,
+        BlendMode, Color, FloatRect, Font, Image, IntRect, PrimitiveType, RectangleShape,
+        RenderTarget, RenderWindow, Texture, Transformable, Vertex, VertexBuffer,
+        VertexBufferUsage,
     },
     system::{Vector2f, Vector2u},
     window::{Event, Key, Style, VideoMode},
@@ -20,28 +35,63 @@ use sfml::{
 use tracing::{debug, error, info};
 
 use bewegrs::{
+    best_video_mode,
     counter::Counter,
-    graphic::{ComprehensiveElement, ComprehensiveUi, elements::info::Info},
+    graphic::{
+        ComprehensiveElement, ComprehensiveUi, UI_Z_LEVEL,
+        bus::EventBus,
+        elements::{
+            help::HelpOverlay,
+            info::Info,
+            reticle::{Reticle, ReticleShape},
+        },
+        projection,
+    },
     setup,
+    shapes::hue_time,
 };
 
 pub const DEFAULT_MAX_FPS: u64 = 60;
 pub const DEFAULT_STAR_AMOUNT: usize = 500_000;
 pub const DEFAULT_SPEED: f32 = 0.8;
-const BG: Color = Color::rgb(30, 20, 20);
+/// How much of the way to the target speed is made in each frame at [DEFAULT_MAX_FPS], see
+/// [Stars::set_acceleration]
+pub const DEFAULT_ACCELERATION: f32 = 0.08;
+/// Values for [Stars::set_acceleration], `1.0` jumps to the target speed at once
+pub const ACCELERATION_RANGE: RangeInclusive<f32> = 0.01..=1.0;
+pub const DEFAULT_BG: Color = Color::rgb(30, 20, 20);
 
 // Star configuration
 pub const DEFAULT_STAR_RADIUS: f32 = 150.0;
-const FAR_PLANE: f32 = 2200.0;
-const NEAR_PLANE: f32 = 5.5;
-const BEHIND_CAMERA: f32 = 60.5;
-const SPREAD: f32 = FAR_PLANE * 40.0;
-
-const UPDATE_TIERS: &[(std::ops::Range<u8>, u64)] = &[
+pub const DEFAULT_FAR_PLANE: f32 = 2200.0;
+pub const DEFAULT_NEAR_PLANE: f32 = 5.5;
+pub const DEFAULT_BEHIND_CAMERA: f32 = 60.5;
+pub const DEFAULT_SPREAD: f32 = DEFAULT_FAR_PLANE * 40.0;
+/// How far the camera can be moved sideways with the mouse, in world units
+const MAX_PAN: f32 = 2000.0;
+/// How much of its brightness a twinkling star loses at most
+const TWINKLE_DEPTH: f32 = 0.5;
+/// The speed snaps to the target speed once it is this close
+const SPEED_SETTLE_THRESHOLD: f32 = 0.001;
+/// How many updates a recycled star takes to fade in, if [Stars::set_fade_in] is on
+const FADE_IN_FRAMES: u16 = 30;
+/// Width of the minimap, relative to the width of the screen
+const MINIMAP_SCALE: f32 = 0.2;
+/// Distance of the minimap to the corner of the screen, in pixels
+const MINIMAP_MARGIN: f32 = 10.0;
+/// Background of the minimap, so that it can be told apart from the stars behind it
+const MINIMAP_BG: Color = Color::rgba(0, 0, 0, 160);
+/// Stars get their random numbers in chunks of this size, each chunk with its own rng. This does
+/// not depend on the amount of threads, so a seed gives the same stars on every machine.
+const RNG_CHUNK_SIZE: usize = 4096;
+
+/// How often the vertices of the stars are updated, by their distance to the nearest visible star
+/// in percent of all stars. See [Stars::set_update_tiers].
+pub const DEFAULT_UPDATE_TIERS: &[(Range<u8>, u64)] = &[
     (00..10, 1),  // From nearest star to nearest+10% - every frame
     (10..30, 2),  // From nearest+10% to nearest+30% - every 2 frames
     (30..60, 4),  // From nearest+30% to nearest+60% - every 4 frames
-    (60..100, 8), // From nearest+60% to end - every 4 frames
+    (60..100, 8), // From nearest+60% to end - every 8 frames
 ];
 
 // export this so that we can use benchmarks
@@ -51,6 +101,12 @@ pub fn stars(args: Vec<String>) -> BwgResult<()> {
     let mut opts = Options::new();
     opts.optopt("s", "stars", "amount of stars", "STARS");
     opts.optopt("i", "sprite", "sprite texture to use for stars", "IMAGE");
+    opts.optopt(
+        "",
+        "preset",
+        "use an embedded sprite for the stars (dot, cross, ring)",
+        "NAME",
+    );
     opts.optflag("h", "help", "print help menu");
     opts.optflag("l", "hide-logo", "hide the logo");
     opts.optflag("v", "verbose", "log more");
@@ -63,6 +119,79 @@ pub fn stars(args: Vec<String>) -> BwgResult<()> {
         "RADIUS",
     );
     opts.optopt("e", "exit-after", "exit after SECS seconds", "SECS");
+    opts.optopt(
+        "c",
+        "color-theme",
+        "color the stars by distance (monochrome, warm, cool, rainbow)",
+        "THEME",
+    );
+    opts.optopt(
+        "b",
+        "bg",
+        "set the background color as hex (default #1e1414)",
+        "COLOR",
+    );
+    opts.optopt(
+        "",
+        "fog",
+        "color that far away stars fade to, as hex (default #000000)",
+        "COLOR",
+    );
+    opts.optopt(
+        "w",
+        "windowed",
+        "run in a window with the given size instead of fullscreen",
+        "WxH",
+    );
+    opts.optopt(
+        "",
+        "seed",
+        "seed for the star positions, for a reproducible star field",
+        "SEED",
+    );
+    opts.optflag(
+        "a",
+        "additive",
+        "blend the stars additively, so overlapping stars get brighter",
+    );
+    opts.optflag(
+        "t",
+        "twinkle",
+        "let the brightness of the stars change over time",
+    );
+    opts.optflag(
+        "",
+        "fade-in",
+        "let recycled stars fade in instead of popping up",
+    );
+    opts.optflag("", "flip-x", "mirror the star sprite horizontally");
+    opts.optflag("", "flip-y", "mirror the star sprite vertically");
+    opts.optflag("", "reticle", "mark the center of the screen");
+    opts.optflag(
+        "",
+        "vsync",
+        "sync to the display instead of limiting the fps, which can be smoother",
+    );
+    opts.optopt(
+        "",
+        "distribution",
+        "how the initial star distances are picked (uniform, volume)",
+        "DIST",
+    );
+    #[cfg(feature = "serde")]
+    opts.optopt(
+        "",
+        "config",
+        "load the star field parameters from a json file, other options override them",
+        "PATH",
+    );
+    #[cfg(feature = "serde")]
+    opts.optopt(
+        "",
+        "save-config",
+        "save the star field parameters to a json file",
+        "PATH",
+    );
     let matches = match opts.parse(&args[1..]) {
         Ok(m) => m,
         Err(f) => {
@@ -80,11 +209,27 @@ pub fn stars(args: Vec<String>) -> BwgResult<()> {
     if let Some(path) = &sprite_path {
         info!("using sprite: {}", path.to_string_lossy());
     }
+    let sprite_memory = match matches.opt_str("preset") {
+        Some(name) => {
+            info!("using sprite preset: {name}");
+            Some(preset_sprite(&name)?)
+        }
+        None => None,
+    };
+
+    let mut config = StarsConfig::default();
+    #[cfg(feature = "serde")]
+    if let Some(path) = matches.opt_str("config") {
+        config = StarsConfig::load(path.as_ref())?;
+        info!("loaded config from {path}");
+    }
 
-    let stars_amount: usize = matches
+    if let Some(amount) = matches
         .opt_get("stars")
         .expect("could not get stars option")
-        .unwrap_or(DEFAULT_STAR_AMOUNT);
+    {
+        config.amount = amount;
+    }
 
     let fps_limit: u64 = matches
         .opt_get("fps")
@@ -92,25 +237,49 @@ pub fn stars(args: Vec<String>) -> BwgResult<()> {
         .unwrap_or(DEFAULT_MAX_FPS);
     info!("fps limit: {fps_limit}");
 
-    let radius: f32 = matches
-        .opt_get("radius")
-        .expect("could not radius option")
-        .unwrap_or(DEFAULT_STAR_RADIUS);
-    info!("radius: {radius}");
+    if let Some(radius) = matches.opt_get("radius").expect("could not radius option") {
+        config.radius = radius;
+    }
+    info!("radius: {}", config.radius);
 
     let exit_after: Option<u64> = matches
         .opt_get("exit-after")
         .expect("could not get fps option");
     info!("exit_after: {exit_after:?}");
 
-    let video = VideoMode::fullscreen_modes()[0];
+    let bg: Color = match matches.opt_str("bg") {
+        Some(hex) => parse_hex_color(&hex)?,
+        None => DEFAULT_BG,
+    };
+    info!("background: {bg:?}");
+
+    let fog: Color = match matches.opt_str("fog") {
+        Some(hex) => parse_hex_color(&hex)?,
+        None => Color::BLACK,
+    };
+    info!("fog: {fog:?}");
+
+    if let Some(palette) = matches.opt_get("color-theme")? {
+        config.palette = palette;
+    }
+    info!("palette: {:?}", config.palette);
+
+    if let Some(seed) = matches.opt_get("seed").expect("could not get seed option") {
+        config.seed = Some(seed);
+    }
+    info!("seed: {:?}", config.seed);
+
+    if let Some(distribution) = matches.opt_get("distribution")? {
+        config.distribution = distribution;
+    }
+    info!("distribution: {:?}", config.distribution);
+
+    let (video, style) = match matches.opt_str("windowed") {
+        Some(size) => (parse_resolution(&size)?, Style::DEFAULT | Style::CLOSE),
+        None => (best_video_mode()?, Style::DEFAULT | Style::FULLSCREEN),
+    };
     info!("video mode: {video:?}");
-    let mut window = RenderWindow::new(
-        video,
-        "Starfield",
-        Style::DEFAULT | Style::FULLSCREEN,
-        &Default::default(),
-    )?;
+    let mut window = RenderWindow::new(video, "Starfield", style, &Default::default())?;
 
     let mut font = Font::new()?;
     font.load_from_memory_static(include_bytes!("../../../resources/sansation.ttf"))?;
@@ -121,21 +290,57 @@ pub fn stars(args: Vec<String>) -> BwgResult<()> {
 
     let mut gui = ComprehensiveUi::build(&mut window, &font, &video, fps_limit)?;
     gui.set_no_cursor(&mut window, true);
+    if matches.opt_present("vsync") {
+        gui.set_vsync(&mut window, true);
+    }
+    // the HUD has a lot of entries, wrap them into columns before they run off the screen
+    let line_height = font.line_spacing(Info::DEFAULT_FONT_SIZE);
+    gui.info
+        .set_max_rows((video.height as f32 * 0.9 / line_height) as usize);
 
     if !matches.opt_present("hide-logo") {
         gui.info
             .set_logo(&texture, "Christoph J. Scherr\nsoftware@cscherr.de")?;
     }
 
-    let stars = Stars::new(video, stars_amount, sprite_path, fps_limit, radius)?;
+    let mut stars = Stars::builder(video)
+        .config(config)
+        .sprite_path(sprite_path)
+        .sprite_memory(sprite_memory)
+        .fps_limit(fps_limit)
+        .build()?;
+    stars.set_bg(bg);
+    stars.set_fog_color(fog);
+    if matches.opt_present("additive") {
+        stars.set_blend_mode(BlendMode::ADD);
+    }
+    stars.set_twinkle(matches.opt_present("twinkle"));
+    stars.set_fade_in(matches.opt_present("fade-in"));
+    stars.set_flip_x(matches.opt_present("flip-x"));
+    stars.set_flip_y(matches.opt_present("flip-y"));
+    #[cfg(feature = "serde")]
+    if let Some(path) = matches.opt_str("save-config") {
+        config.save(path.as_ref())?;
+        info!("saved config to {path}");
+    }
+
+    gui.info.set_section("starfield");
     gui.info.set_custom_info("stars", stars.stars.len());
-    gui.info.set_custom_info("star_r", radius);
-    gui.info.set_custom_info("far", FAR_PLANE);
-    gui.info.set_custom_info("near", NEAR_PLANE);
-    gui.info.set_custom_info("spread", SPREAD);
-    gui.info.set_custom_info("behind_cam", BEHIND_CAMERA);
+    gui.info.set_custom_info("star_r", config.radius);
+    gui.info.set_custom_info("far", config.far_plane);
+    gui.info.set_custom_info("near", config.near_plane);
+    gui.info.set_custom_info("spread", config.spread);
+    gui.info.set_custom_info("behind_cam", config.behind_camera);
+    gui.info
+        .set_custom_info("palette", format_args!("{:?}", config.palette));
+    gui.info
+        .set_custom_info("distribution", format_args!("{:?}", config.distribution));
+    gui.info
+        .set_custom_info("additive", matches.opt_present("additive"));
+    gui.info.set_custom_info("twinkle", stars.twinkle());
     gui.info
-        .set_custom_info("speed", format_args!("{:.03}", DEFAULT_SPEED));
+        .set_custom_info("speed", format_args!("{:.03}", config.speed));
+    gui.info.set_section("system");
     gui.info
         .set_custom_info("threadool_threads", rayon::current_num_threads());
     gui.info.set_custom_info(
@@ -145,12 +350,29 @@ pub fn stars(args: Vec<String>) -> BwgResult<()> {
             video.width, video.height, video.bits_per_pixel
         ),
     );
-    gui.add(Box::new(stars));
+    let (step_toggle, step) = ComprehensiveUi::DEFAULT_STEP_KEYS;
+    gui.set_step_keys(Some((step_toggle, step)));
+    let mut bindings = stars.keymap().bindings();
+    bindings.extend(
+        [
+            ("F10".to_string(), "switch the info display"),
+            (format!("{step_toggle:?}"), "toggle step mode"),
+            (format!("{step:?}"), "next frame in step mode"),
+            ("Escape".to_string(), "quit"),
+        ]
+        .map(|(key, action)| (key, action.to_string())),
+    );
+    let stars_id = gui.add_named("stars", Box::new(stars));
+    gui.add_named("help", Box::new(HelpOverlay::new(&font, bindings)));
+    if matches.opt_present("reticle") {
+        gui.add_named("reticle", Box::new(Reticle::new(ReticleShape::Cross)?));
+    }
 
     let mut logo = RectangleShape::new();
 
     logo.set_position((400.0, 400.0));
     debug!("{logo:?}");
+    gui.add_drawable(Box::new(logo), UI_Z_LEVEL);
 
     'mainloop: loop {
         while let Some(event) = window.poll_event() {
@@ -171,11 +393,10 @@ pub fn stars(args: Vec<String>) -> BwgResult<()> {
             gui.update_slow();
         }
 
-        window.clear(BG);
+        let bg = gui.get_as::<Stars>(&stars_id).map_or(DEFAULT_BG, Stars::bg);
+        window.clear(bg);
         gui.draw_with(&mut window);
 
-        window.draw(&logo);
-
         gui.display(&mut window);
 
         if let Some(secs) = exit_after {
@@ -185,18 +406,92 @@ pub fn stars(args: Vec<String>) -> BwgResult<()> {
         }
     }
 
-    let frames = gui.counter.frames;
-    let secs = gui.counter.seconds;
-    info!(
-        "{} frames in {} seconds ({:02.04}ms per frame)",
-        frames,
-        secs,
-        secs * 1000.0 / frames as f32
-    );
+    if let Some(stars) = gui.get_as::<Stars>(&stars_id) {
+        info!("final speed: {:.03}", stars.speed);
+    }
+
+    info!("{}", gui.counter.summary());
 
     Ok(())
 }
 
+/// Creates the rng for one chunk of stars. With a seed, the same `stream` always gives the same
+/// rng, without one it is seeded from the thread rng.
+fn chunk_rng(seed: Option<u64>, stream: u64) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed ^ stream.wrapping_mul(0x9E37_79B9_7F4A_7C15)),
+        None => StdRng::from_rng(&mut rand::rng()),
+    }
+}
+
+/// Parses a color in the form of `#RGB`, `#RRGGBB` or `#RRGGBBAA`. The leading `#` is optional.
+pub fn parse_hex_color(s: &str) -> BwgResult<Color> {
+    let hex = s.strip_prefix('#').unwrap_or(s);
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(
+            anyhow!("invalid color '{s}': may only contain hex digits after the '#'").into(),
+        );
+    }
+
+    // all chars are ascii hex digits, so slicing and parsing can not fail
+    let channel = |idx: usize, width: usize| -> u8 {
+        let value = u8::from_str_radix(&hex[idx * width..(idx + 1) * width], 16)
+            .expect("hex digits were checked before");
+        if width == 1 { value * 0x11 } else { value }
+    };
+
+    match hex.len() {
+        3 => Ok(Color::rgb(channel(0, 1), channel(1, 1), channel(2, 1))),
+        6 => Ok(Color::rgb(channel(0, 2), channel(1, 2), channel(2, 2))),
+        8 => Ok(Color::rgba(
+            channel(0, 2),
+            channel(1, 2),
+            channel(2, 2),
+            channel(3, 2),
+        )),
+        _ => Err(anyhow!("invalid color '{s}': expected #RGB, #RRGGBB or #RRGGBBAA").into()),
+    }
+}
+
+/// Parses a resolution in the form of `WIDTHxHEIGHT`, like `1280x720`
+pub fn parse_resolution(s: &str) -> BwgResult<VideoMode> {
+    let invalid = || anyhow!("invalid resolution '{s}': expected WIDTHxHEIGHT, like 1280x720");
+    let (width, height) = s.split_once(['x', 'X']).ok_or_else(invalid)?;
+    let width: u32 = width.trim().parse().map_err(|_| invalid())?;
+    let height: u32 = height.trim().parse().map_err(|_| invalid())?;
+    if width == 0 || height == 0 {
+        return Err(invalid().into());
+    }
+    Ok(VideoMode::new(width, height, 32))
+}
+
+/// Star sprites that are embedded in the binary, see [preset_sprite]
+pub const SPRITE_PRESETS: &[(&str, &[u8])] = &[
+    ("dot", include_bytes!("../../../resources/star_dot.png")),
+    ("cross", include_bytes!("../../../resources/star_cross.png")),
+    ("ring", include_bytes!("../../../resources/star_ring.png")),
+];
+
+/// The embedded image of one of the [SPRITE_PRESETS], for [StarsBuilder::sprite_memory]
+pub fn preset_sprite(name: &str) -> BwgResult<&'static [u8]> {
+    SPRITE_PRESETS
+        .iter()
+        .find(|(preset, _)| *preset == name)
+        .map(|(_, data)| *data)
+        .ok_or_else(|| {
+            let names: Vec<&str> = SPRITE_PRESETS.iter().map(|(preset, _)| *preset).collect();
+            anyhow!("unknown sprite preset '{name}', expected one of {names:?}").into()
+        })
+}
+
+/// Loads one of the [SPRITE_PRESETS] as a texture
+pub fn preset_texture(name: &str) -> BwgResult<FBox<Texture>> {
+    let image = Image::from_memory(preset_sprite(name)?)?;
+    let mut texture = Texture::from_image(&image, IntRect::default())?;
+    texture.set_smooth(true);
+    Ok(texture)
+}
+
 fn print_usage(program: &str, opts: Options) {
     let brief = format!("Usage: {} [options]", program,);
     print!(
@@ -207,6 +502,277 @@ fn print_usage(program: &str, opts: Options) {
     );
 }
 
+/// How stars are colored, depending on their distance
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StarPalette {
+    /// the color of the star texture
+    #[default]
+    Monochrome,
+    /// from bright yellow in the front to orange in the back
+    Warm,
+    /// from pale blue in the front to deep blue in the back
+    Cool,
+    /// cycles through the hues with the distance
+    Rainbow,
+}
+
+impl StarPalette {
+    /// Color of a star before it gets darker with distance. `depth_ratio` is `0.0` on the
+    /// near plane and `1.0` on the far plane.
+    fn color(self, texture_color: Color, depth_ratio: f32) -> Color {
+        let depth_ratio = depth_ratio.clamp(0.0, 1.0);
+        let gradient = |near: [f32; 3], far: [f32; 3]| {
+            let [r, g, b] = [0, 1, 2].map(|i| (near[i] + depth_ratio * (far[i] - near[i])) as u8);
+            Color::rgb(r, g, b)
+        };
+        match self {
+            Self::Monochrome => texture_color,
+            Self::Warm => gradient([255.0, 240.0, 200.0], [255.0, 110.0, 40.0]),
+            Self::Cool => gradient([220.0, 240.0, 255.0], [60.0, 100.0, 255.0]),
+            Self::Rainbow => hue_time(depth_ratio * 5.0),
+        }
+    }
+}
+
+impl FromStr for StarPalette {
+    type Err = BwgError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_lowercase().as_str() {
+            "monochrome" => Self::Monochrome,
+            "warm" => Self::Warm,
+            "cool" => Self::Cool,
+            "rainbow" => Self::Rainbow,
+            _ => {
+                return Err(anyhow!(
+                    "unknown color theme '{s}': expected monochrome, warm, cool or rainbow"
+                )
+                .into());
+            }
+        })
+    }
+}
+
+/// How the distances of new stars are picked
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpawnDistribution {
+    /// every distance between the near and the far plane is equally likely
+    #[default]
+    Uniform,
+    /// the stars are spread evenly through the volume in front of the camera, so there are more
+    /// of them far away, where a depth band covers a larger area
+    VolumeWeighted,
+}
+
+impl SpawnDistribution {
+    /// Maps `u` in `0.0..1.0` to a distance between `near` and `far`
+    fn distance(self, near: f32, far: f32, u: f32) -> f32 {
+        match self {
+            Self::Uniform => near + u * (far - near),
+            // the volume up to a distance grows with its cube, so sample the cube uniformly
+            Self::VolumeWeighted => {
+                let (near3, far3) = (near.powi(3), far.powi(3));
+                (near3 + u * (far3 - near3)).cbrt()
+            }
+        }
+    }
+}
+
+impl FromStr for SpawnDistribution {
+    type Err = BwgError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_lowercase().as_str() {
+            "uniform" => Self::Uniform,
+            "volume" | "volume-weighted" => Self::VolumeWeighted,
+            _ => {
+                return Err(
+                    anyhow!("unknown distribution '{s}': expected uniform or volume").into(),
+                );
+            }
+        })
+    }
+}
+
+/// Tunable parameters of a [Stars] field, see [StarsBuilder]
+///
+/// With the `serde` feature, this can be saved to and loaded from json. Missing fields use the
+/// defaults.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct StarsConfig {
+    pub amount: usize,
+    pub radius: f32,
+    /// the speed the stars start with
+    pub speed: f32,
+    /// stars start at most this far away, and are reset when flying further away
+    pub far_plane: f32,
+    /// distance of the screen from the camera, stars are projected onto it
+    pub near_plane: f32,
+    /// how far stars fly past the camera before they are reset
+    pub behind_camera: f32,
+    /// how far stars are spread around the center, in world units
+    pub spread: f32,
+    /// seed for the random star positions, the same seed always gives the same stars
+    pub seed: Option<u64>,
+    /// how the distances of the initial stars are picked
+    pub distribution: SpawnDistribution,
+    pub palette: StarPalette,
+}
+
+impl Default for StarsConfig {
+    fn default() -> Self {
+        Self {
+            amount: DEFAULT_STAR_AMOUNT,
+            radius: DEFAULT_STAR_RADIUS,
+            speed: DEFAULT_SPEED,
+            far_plane: DEFAULT_FAR_PLANE,
+            near_plane: DEFAULT_NEAR_PLANE,
+            behind_camera: DEFAULT_BEHIND_CAMERA,
+            spread: DEFAULT_SPREAD,
+            seed: None,
+            distribution: SpawnDistribution::Uniform,
+            palette: StarPalette::Monochrome,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl StarsConfig {
+    /// Reads a config from a json file
+    pub fn load(path: &Path) -> BwgResult<Self> {
+        let file = File::open(path)
+            .with_context(|| format!("could not open the config {}", path.display()))?;
+        let config = serde_json::from_reader(BufReader::new(file))
+            .with_context(|| format!("could not parse the config {}", path.display()))?;
+        Ok(config)
+    }
+
+    /// Writes the config to a json file, replacing it if it exists
+    pub fn save(&self, path: &Path) -> BwgResult<()> {
+        let file = File::create(path)
+            .with_context(|| format!("could not create the config {}", path.display()))?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self)
+            .with_context(|| format!("could not write the config {}", path.display()))?;
+        Ok(())
+    }
+}
+
+/// Builds [Stars], everything that is not set uses the defaults of [StarsConfig]
+pub struct StarsBuilder {
+    video: VideoMode,
+    config: StarsConfig,
+    sprite_path: Option<PathBuf>,
+    sprite_memory: Option<&'static [u8]>,
+    fps_limit: u64,
+}
+
+impl StarsBuilder {
+    pub fn new(video: VideoMode) -> Self {
+        Self {
+            video,
+            config: StarsConfig::default(),
+            sprite_path: None,
+            sprite_memory: None,
+            fps_limit: DEFAULT_MAX_FPS,
+        }
+    }
+
+    pub fn config(mut self, config: StarsConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn amount(mut self, amount: usize) -> Self {
+        self.config.amount = amount;
+        self
+    }
+
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.config.radius = radius;
+        self
+    }
+
+    pub fn speed(mut self, speed: f32) -> Self {
+        self.config.speed = speed;
+        self
+    }
+
+    pub fn far_plane(mut self, far_plane: f32) -> Self {
+        self.config.far_plane = far_plane;
+        self
+    }
+
+    pub fn near_plane(mut self, near_plane: f32) -> Self {
+        self.config.near_plane = near_plane;
+        self
+    }
+
+    pub fn behind_camera(mut self, behind_camera: f32) -> Self {
+        self.config.behind_camera = behind_camera;
+        self
+    }
+
+    pub fn spread(mut self, spread: f32) -> Self {
+        self.config.spread = spread;
+        self
+    }
+
+    /// Make the star field reproducible, `None` gives different stars every time
+    pub fn seed(mut self, seed: Option<u64>) -> Self {
+        self.config.seed = seed;
+        self
+    }
+
+    pub fn palette(mut self, palette: StarPalette) -> Self {
+        self.config.palette = palette;
+        self
+    }
+
+    pub fn distribution(mut self, distribution: SpawnDistribution) -> Self {
+        self.config.distribution = distribution;
+        self
+    }
+
+    /// Use an image file as the star texture instead of the default star
+    pub fn sprite_path(mut self, sprite_path: Option<PathBuf>) -> Self {
+        self.sprite_path = sprite_path;
+        self
+    }
+
+    /// Use an image in memory as the star texture, like one of the [SPRITE_PRESETS]
+    ///
+    /// A [sprite path](Self::sprite_path) takes precedence over this.
+    pub fn sprite_memory(mut self, sprite_memory: Option<&'static [u8]>) -> Self {
+        self.sprite_memory = sprite_memory;
+        self
+    }
+
+    pub fn fps_limit(mut self, fps_limit: u64) -> Self {
+        self.fps_limit = fps_limit;
+        self
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the near plane is not between `0.0` and the far plane, or if the spread is not
+    /// positive.
+    pub fn build(self) -> SfResult<Stars> {
+        let c = &self.config;
+        assert!(
+            0.0 < c.near_plane && c.near_plane < c.far_plane,
+            "the near plane ({}) must be between 0 and the far plane ({})",
+            c.near_plane,
+            c.far_plane
+        );
+        assert!(c.spread > 0.0, "the spread ({}) must be positive", c.spread);
+        Stars::from_builder(self)
+    }
+}
+
 #[derive(Default, Clone, Copy)]
 pub struct Star {
     /// World-space position (centered around 0,0)
@@ -215,31 +781,152 @@ pub struct Star {
     active: bool,
     rotation: f32,
     rotation_speed: f32,
+    /// offset of the twinkle wave, so that stars do not twinkle in sync
+    twinkle_phase: f32,
+    /// how far the twinkle wave moves per frame
+    twinkle_speed: f32,
+    /// updates since the star was recycled, up to [FADE_IN_FRAMES]
+    age: u16,
+}
+
+/// The keys that control [Stars], see [Stars::set_keymap]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Keymap {
+    /// faster with shift
+    pub speed_up: Key,
+    /// faster with shift
+    pub speed_down: Key,
+    /// only works together with shift, so that the stars are not stopped by accident
+    pub stop: Key,
+    pub pause: Key,
+    pub reset: Key,
+}
+
+impl Keymap {
+    /// Describes every binding, like `("W", "speed up (faster with shift)")`
+    pub fn bindings(&self) -> Vec<(String, String)> {
+        vec![
+            (
+                format!("{:?}", self.speed_up),
+                "speed up (faster with shift)".to_string(),
+            ),
+            (
+                format!("{:?}", self.speed_down),
+                "slow down (faster with shift)".to_string(),
+            ),
+            (format!("Shift+{:?}", self.stop), "stop".to_string()),
+            (format!("{:?}", self.pause), "pause".to_string()),
+            (format!("{:?}", self.reset), "reset the stars".to_string()),
+        ]
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            speed_up: Key::W,
+            speed_down: Key::S,
+            stop: Key::Space,
+            pause: Key::P,
+            reset: Key::R,
+        }
+    }
 }
 
+/// Message that makes every [Stars] element [reset](Stars::reset)
+///
+/// The reset key and the reset button of the egui controls post it, the app can post it with
+/// [ComprehensiveUi::post].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResetStars;
+
 pub struct Stars {
     stars: Vec<Star>,
     star_vertices_buf: FBox<VertexBuffer>,
     star_vertices: Vec<Vertex>,
     video: VideoMode,
     speed: f32,
+    /// the speed is eased towards this, set with the keys
+    target_speed: f32,
+    /// how fast the speed reaches the target speed, see [Stars::set_acceleration]
+    acceleration: f32,
     texture: FBox<Texture>,
     last_sorted_frame: u64,
+    /// how often the vertices of each depth band are updated, see [DEFAULT_UPDATE_TIERS]
+    update_tiers: Vec<(Range<u8>, u64)>,
+    /// indices of the stars that were recycled in this frame and are not sorted anymore
+    dirty: Vec<usize>,
     texture_size: Vector2u,
     texture_color: Color,
     keyframe: bool,
-    radius: f32,
+    config: StarsConfig,
+    bg: Color,
+    /// sideways offset of the camera, controlled by the mouse
+    pan: Vector2f,
+    /// stars are still drawn, but neither moved nor sorted
+    paused: bool,
+    /// width / height of the screen
+    aspect_ratio: f32,
+    spawn_area: SpawnArea,
+    /// how the stars are blended with each other and the background
+    blend_mode: BlendMode,
+    /// the brightness of the stars changes over time
+    twinkle: bool,
+    /// far away stars fade to this color instead of the full color of the palette
+    fog_color: Color,
+    /// mirror the sprite on every star horizontally
+    flip_x: bool,
+    /// mirror the sprite on every star vertically
+    flip_y: bool,
+    /// recycled stars fade in instead of popping up at full brightness
+    fade_in: bool,
+    /// draw a top-down overview of the field in the corner, see [Stars::draw_minimap]
+    minimap: bool,
+    /// one point per star, reused for every [Stars::draw_minimap]
+    minimap_vertices: Vec<Vertex>,
+    minimap_buf: FBox<VertexBuffer>,
+    keymap: Keymap,
+}
+
+/// Where stars are placed when they are (re)spawned, computed once from the screen size
+#[derive(Debug, Clone, Copy)]
+struct SpawnArea {
+    /// stars are placed in `-half_extent..half_extent` around the center
+    half_extent: Vector2f,
+    /// no stars are placed in here
+    star_free: FloatRect,
+}
+
+impl SpawnArea {
+    fn new(width: u32, height: u32, aspect_ratio: f32, config: &StarsConfig) -> Self {
+        SpawnArea {
+            // only x is stretched, so that a wide screen is covered just like a narrow one without
+            // squishing the stars
+            half_extent: Vector2f::new(config.spread * aspect_ratio, config.spread),
+            star_free: FloatRect::new(
+                width as f32 / -2.0,
+                height as f32 / -2.0,
+                width as f32 * 0.7,
+                height as f32 * 0.7,
+            ),
+        }
+    }
 }
 
 struct StarRenderCtx<'render> {
     width: u32,
     height: u32,
-    vertices: &'render mut [Vertex],
-    index: usize,
     texture_size: &'render Vector2u,
     color: &'render Color,
-    aspect_ratio: f32,
-    radius: f32,
+    config: &'render StarsConfig,
+    palette: StarPalette,
+    pan: Vector2f,
+    twinkle: bool,
+    fog_color: Color,
+    flip_x: bool,
+    flip_y: bool,
+    fade_in: bool,
+    frame: u64,
 }
 
 impl Star {
@@ -250,108 +937,162 @@ impl Star {
             active: true,
             rotation: 0.0,
             rotation_speed: 0.0,
+            twinkle_phase: 0.0,
+            twinkle_speed: 0.0,
+            age: FADE_IN_FRAMES,
         }
     }
 
-    fn randomize(&mut self, width: u32, height: u32) {
-        self.rand_pos(width, height);
-        self.distance = Star::rand_distance();
-        self.rotation = rand::random_range(0.0..std::f32::consts::PI * 2.0);
-        self.rotation_speed = (rand::random::<f32>() - 0.5) * 0.05;
+    fn randomize<R: Rng + ?Sized>(&mut self, area: &SpawnArea, config: &StarsConfig, rng: &mut R) {
+        self.rand_pos(area, rng);
+        self.distance = Star::rand_distance(config, rng);
+        self.rotation = rng.random_range(0.0..std::f32::consts::PI * 2.0);
+        self.rotation_speed = (rng.random::<f32>() - 0.5) * 0.05;
+        self.twinkle_phase = rng.random_range(0.0..std::f32::consts::PI * 2.0);
+        self.twinkle_speed = rng.random_range(0.02..0.1);
+        // stars that are placed all at once are there from the start, they do not fade in
+        self.age = FADE_IN_FRAMES;
     }
 
     #[inline]
-    fn rand_distance() -> f32 {
-        rand::random_range(NEAR_PLANE..FAR_PLANE)
+    fn rand_distance<R: Rng + ?Sized>(config: &StarsConfig, rng: &mut R) -> f32 {
+        config
+            .distribution
+            .distance(config.near_plane, config.far_plane, rng.random())
     }
 
-    fn rand_pos(&mut self, width: u32, height: u32) {
+    fn rand_pos<R: Rng + ?Sized>(&mut self, area: &SpawnArea, rng: &mut R) {
         // Generate position centered around origin in world space
-        // Scale by FAR_PLANE to give stars enough space
-        let aspect_ratio = width as f32 / height as f32;
-        let star_free = FloatRect::new(
-            width as f32 / -2.0,
-            height as f32 / -2.0,
-            width as f32 * 0.7,
-            height as f32 * 0.7,
-        );
+        let half = area.half_extent;
         loop {
             self.position = Vector2f::new(
-                rand::random_range(-SPREAD..SPREAD),
-                rand::random_range(-SPREAD..SPREAD),
-            ) * aspect_ratio;
-            if !star_free.contains(self.position) {
+                rng.random_range(-half.x..half.x),
+                rng.random_range(-half.y..half.y),
+            );
+            if !area.star_free.contains(self.position) {
                 break;
             }
         }
     }
 
-    fn update(&mut self, speed: f32, width: u32, height: u32, fps_limit: u64) {
+    fn update<R: Rng + ?Sized>(
+        &mut self,
+        speed: f32,
+        area: &SpawnArea,
+        fps_limit: u64,
+        config: &StarsConfig,
+        rng: &mut R,
+    ) -> bool {
         self.distance -= speed * (DEFAULT_MAX_FPS as f32 / fps_limit as f32);
 
         self.rotation += self.rotation_speed;
 
-        // If star gets too close, reset it
-        if self.distance <= -BEHIND_CAMERA {
-            self.rand_pos(width, height);
-            self.distance = FAR_PLANE;
+        let mut recycled = true;
+        // If star gets too close, reset it. All comparisons with NaN are false, so a star with a
+        // NaN distance would never be reset otherwise. This can happen after extreme speed
+        // changes.
+        if !self.distance.is_finite() || self.distance <= -config.behind_camera {
+            self.rand_pos(area, rng);
+            self.distance = config.far_plane;
         }
         // If star gets too far, reset it
-        else if self.distance >= FAR_PLANE {
-            self.rand_pos(width, height);
-            self.distance = -BEHIND_CAMERA;
+        else if self.distance >= config.far_plane {
+            self.rand_pos(area, rng);
+            self.distance = -config.behind_camera;
+        } else {
+            recycled = false;
         }
+        self.age = if recycled {
+            0
+        } else {
+            (self.age + 1).min(FADE_IN_FRAMES)
+        };
 
-        // NOTE: setting these to constant values is important, because otherwise, we need to sort
-        // the star array again. Otherwise, far stars would get rendered over near stars
+        // NOTE: all other stars move by the same amount, so only the recycled stars need to be put
+        // into their sorted place again. Otherwise, far stars would get rendered over near stars
 
-        self.active = self.is_visible();
+        debug_assert!(
+            self.distance.is_finite(),
+            "star distance is not finite after update: {}",
+            self.distance
+        );
+        self.active = self.is_visible(config);
+        recycled
     }
 
     #[inline]
-    fn is_visible(&self) -> bool {
+    fn is_visible(&self, config: &StarsConfig) -> bool {
         // Check if star is big enough to see
-        NEAR_PLANE / self.distance > 0.001
+        config.near_plane / self.distance > 0.001
+    }
+
+    /// Factor for the brightness, between `1.0 - TWINKLE_DEPTH` and `1.0`
+    #[inline]
+    fn twinkle(&self, ctx: &StarRenderCtx) -> f32 {
+        if !ctx.twinkle {
+            return 1.0;
+        }
+        let wave = (self.twinkle_phase + ctx.frame as f32 * self.twinkle_speed).sin();
+        1.0 - TWINKLE_DEPTH * (0.5 + 0.5 * wave)
     }
 
     // Create vertices for this star (a quad made of 4 vertices)
-    fn update_vertices(&self, ctx: &mut StarRenderCtx) {
+    fn update_vertices(&self, ctx: &StarRenderCtx, quad: &mut [Vertex]) {
         if !self.active {
             // Make vertices transparent for skipped stars
-            let i = ctx.index * 4;
-            for j in 0..4 {
-                ctx.vertices[i + j].color = Color::TRANSPARENT;
+            for vertex in quad.iter_mut() {
+                vertex.color = Color::TRANSPARENT;
             }
             return;
         }
 
-        // Create the 4 vertices of the quad (one star = 4 vertices)
-        let i = ctx.index * 4;
+        let (near, far) = (ctx.config.near_plane, ctx.config.far_plane);
 
         // Calculate perspective scale factor
-        let scale = NEAR_PLANE / self.distance;
+        let scale = projection::scale(self.distance, near);
 
         // Calculate projected screen position (center of star)
-        let screen_x = self.position.x * scale * ctx.aspect_ratio + ctx.width as f32 / 2.0;
-        let screen_y = self.position.y * scale + ctx.height as f32 / 2.0;
+        // The pan is applied before the projection, so near stars move more than far stars
+        let Vector2f {
+            x: screen_x,
+            y: screen_y,
+        } = projection::project(
+            self.position - ctx.pan,
+            self.distance,
+            near,
+            Vector2u::new(ctx.width, ctx.height),
+        );
 
         // Depth ratio for color
-        let depth_ratio = (self.distance - NEAR_PLANE) / (FAR_PLANE - NEAR_PLANE);
-        let brightness = ((1.0 - depth_ratio) * 255.0) as u8;
-
-        // Calculate radius based on distance
-        let radius = ctx.radius * scale;
+        let depth_ratio = projection::depth_ratio(self.distance, near, far);
+        let brightness =
+            (projection::brightness(self.distance, near, far) as f32 * self.twinkle(ctx)) as u8;
+
+        // Calculate radius based on distance, the longer side of the sprite gets the full radius
+        let radius = ctx.config.radius * scale;
+        let aspect = texture_aspect(*ctx.texture_size);
+        let (radius_x, radius_y) = if aspect >= 1.0 {
+            (radius, radius / aspect)
+        } else {
+            (radius * aspect, radius)
+        };
 
+        let color = ctx.palette.color(*ctx.color, depth_ratio);
         let darkness = 255 - brightness;
-        let adjusted_color = Color::rgb(
-            ctx.color.r.saturating_sub(darkness),
-            ctx.color.g.saturating_sub(darkness),
-            ctx.color.b.saturating_sub(darkness),
+        let fog = ctx.fog_color;
+        let mut adjusted_color = Color::rgb(
+            fade_channel(color.r, fog.r, darkness),
+            fade_channel(color.g, fog.g, darkness),
+            fade_channel(color.b, fog.b, darkness),
         );
+        if ctx.fade_in {
+            // only the alpha changes, the position and so the sort order stay the same
+            adjusted_color.a = (self.age as u32 * 255 / FADE_IN_FRAMES as u32) as u8;
+        }
 
         // Set color for all vertices
-        for j in 0..4 {
-            ctx.vertices[i + j].color = adjusted_color;
+        for vertex in quad.iter_mut() {
+            vertex.color = adjusted_color;
         }
 
         // Precalculate sin and cos of rotation angle
@@ -360,10 +1101,10 @@ impl Star {
 
         // Define the four corners relative to center (before rotation)
         let corners = [
-            (-radius, -radius), // Top-left
-            (radius, -radius),  // Top-right
-            (radius, radius),   // Bottom-right
-            (-radius, radius),  // Bottom-left
+            (-radius_x, -radius_y), // Top-left
+            (radius_x, -radius_y),  // Top-right
+            (radius_x, radius_y),   // Bottom-right
+            (-radius_x, radius_y),  // Bottom-left
         ];
 
         // Apply rotation to vertex positions
@@ -375,20 +1116,50 @@ impl Star {
             let rotated_y = corner_x * sin_rot + corner_y * cos_rot;
 
             // Set vertex position
-            ctx.vertices[i + j].position =
-                Vector2f::new(screen_x + rotated_x, screen_y + rotated_y);
+            quad[j].position = Vector2f::new(screen_x + rotated_x, screen_y + rotated_y);
         }
 
         // Get texture dimensions
         let tex_x: f32 = ctx.texture_size.x as f32;
         let tex_y: f32 = ctx.texture_size.y as f32;
 
+        // Mirroring only swaps which side of the texture a corner gets
+        let (left, right) = if ctx.flip_x {
+            (tex_x, 0.0)
+        } else {
+            (0.0, tex_x)
+        };
+        let (top, bottom) = if ctx.flip_y {
+            (tex_y, 0.0)
+        } else {
+            (0.0, tex_y)
+        };
+
         // Set texture coordinates
         // These coordinates align with the rotated vertices to make the texture rotate with the quad
-        ctx.vertices[i].tex_coords = Vector2f::new(0.0, 0.0); // Top-left
-        ctx.vertices[i + 1].tex_coords = Vector2f::new(tex_x, 0.0); // Top-right
-        ctx.vertices[i + 2].tex_coords = Vector2f::new(tex_x, tex_y); // Bottom-right
-        ctx.vertices[i + 3].tex_coords = Vector2f::new(0.0, tex_y); // Bottom-left
+        quad[0].tex_coords = Vector2f::new(left, top); // Top-left
+        quad[1].tex_coords = Vector2f::new(right, top); // Top-right
+        quad[2].tex_coords = Vector2f::new(right, bottom); // Bottom-right
+        quad[3].tex_coords = Vector2f::new(left, bottom); // Bottom-left
+    }
+}
+
+/// Width / height of a texture, `1.0` for an empty one
+fn texture_aspect(size: Vector2u) -> f32 {
+    if size.y == 0 {
+        return 1.0;
+    }
+    size.x as f32 / size.y as f32
+}
+
+/// Moves a color channel by `amount` towards the channel of the fog, without overshooting it
+///
+/// With a black fog, this is the same as `channel.saturating_sub(amount)`.
+fn fade_channel(channel: u8, fog: u8, amount: u8) -> u8 {
+    if channel >= fog {
+        channel.saturating_sub(amount).max(fog)
+    } else {
+        channel.saturating_add(amount).min(fog)
     }
 }
 
@@ -400,7 +1171,33 @@ impl Stars {
         fps_limit: u64,
         radius: f32,
     ) -> SfResult<Self> {
-        let (texture, texture_color) = Self::create_star_texture(sprite_path)?;
+        Self::builder(video)
+            .amount(amount)
+            .sprite_path(sprite_path)
+            .fps_limit(fps_limit)
+            .radius(radius)
+            .build()
+    }
+
+    pub fn builder(video: VideoMode) -> StarsBuilder {
+        StarsBuilder::new(video)
+    }
+
+    /// Creates stars with the default sprite and fps limit, like [StarsBuilder::config]
+    pub fn from_config(video: VideoMode, config: &StarsConfig) -> SfResult<Self> {
+        Self::builder(video).config(*config).build()
+    }
+
+    fn from_builder(builder: StarsBuilder) -> SfResult<Self> {
+        let StarsBuilder {
+            video,
+            config,
+            sprite_path,
+            sprite_memory,
+            fps_limit,
+        } = builder;
+        let amount = config.amount;
+        let (texture, texture_color) = Self::create_star_texture(sprite_path, sprite_memory)?;
 
         info!(
             "Star texture dimensions: {}x{}",
@@ -408,11 +1205,12 @@ impl Stars {
             texture.size().y
         );
 
+        let aspect_ratio = video.width as f32 / video.height as f32;
+        let spawn_area = SpawnArea::new(video.width, video.height, aspect_ratio, &config);
+
         let new_star = Star::new();
         let mut stars: Vec<Star> = vec![new_star; amount];
-        stars
-            .par_iter_mut()
-            .for_each(|star| star.randomize(video.width, video.height));
+        Self::randomize_stars(&mut stars, &spawn_area, &config);
 
         let mut star_vertices = vec![Vertex::default(); amount * 4];
         let mut point_vertices = vec![Vertex::default(); amount];
@@ -426,28 +1224,318 @@ impl Stars {
 
         let star_vertices_buf =
             VertexBuffer::new(PrimitiveType::QUADS, amount * 4, VertexBufferUsage::STREAM)?;
+        let minimap_buf =
+            VertexBuffer::new(PrimitiveType::POINTS, amount, VertexBufferUsage::STREAM)?;
 
         let mut stars = Stars {
             stars,
             star_vertices_buf,
             star_vertices,
             video,
-            speed: DEFAULT_SPEED,
+            speed: config.speed,
+            target_speed: config.speed,
+            acceleration: DEFAULT_ACCELERATION,
             last_sorted_frame: 0,
+            update_tiers: DEFAULT_UPDATE_TIERS.to_vec(),
+            dirty: Vec::new(),
             texture_size: texture.size(),
             texture,
             texture_color,
             keyframe: false,
-            radius,
+            config,
+            bg: DEFAULT_BG,
+            pan: Vector2f::new(0.0, 0.0),
+            paused: false,
+            aspect_ratio,
+            spawn_area,
+            blend_mode: BlendMode::ALPHA,
+            twinkle: false,
+            fog_color: Color::BLACK,
+            flip_x: false,
+            flip_y: false,
+            fade_in: false,
+            minimap: false,
+            minimap_vertices: point_vertices,
+            minimap_buf,
+            keymap: Keymap::default(),
         };
 
         stars.sort(0);
         let ranges = &stars.get_update_ranges(0, fps_limit, stars.stars.len());
-        stars.update_vertex_ranges(ranges)?;
+        stars.update_vertex_ranges(ranges, 0)?;
 
         Ok(stars)
     }
 
+    /// Gives every star a new random position and distance, in parallel
+    fn randomize_stars(stars: &mut [Star], area: &SpawnArea, config: &StarsConfig) {
+        stars
+            .par_chunks_mut(RNG_CHUNK_SIZE)
+            .enumerate()
+            .for_each(|(idx, chunk)| {
+                let mut rng = chunk_rng(config.seed, idx as u64);
+                for star in chunk {
+                    star.randomize(area, config, &mut rng);
+                }
+            });
+    }
+
+    /// Places all stars randomly again, without allocating a new field
+    ///
+    /// With a [seed](StarsConfig::seed), this gives the same field as when the stars were built.
+    pub fn reset(&mut self) {
+        Self::randomize_stars(&mut self.stars, &self.spawn_area, &self.config);
+        self.dirty.clear();
+        // this is not a new frame, so the frame of the last sort stays
+        self.sort(self.last_sorted_frame);
+        // every star moved, so all vertices need to be updated
+        self.keyframe = true;
+    }
+
+    pub fn config(&self) -> &StarsConfig {
+        &self.config
+    }
+
+    /// How much of the way to the target speed is made in each frame, at [DEFAULT_MAX_FPS]
+    ///
+    /// Lower values give a smoother but slower boost. This is clamped to [ACCELERATION_RANGE].
+    pub fn set_acceleration(&mut self, acceleration: f32) {
+        self.acceleration =
+            acceleration.clamp(*ACCELERATION_RANGE.start(), *ACCELERATION_RANGE.end());
+    }
+
+    pub fn acceleration(&self) -> f32 {
+        self.acceleration
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// The speed the stars are accelerating or slowing down to
+    pub fn target_speed(&self) -> f32 {
+        self.target_speed
+    }
+
+    /// Width / height of the screen the stars were made for
+    pub fn aspect_ratio(&self) -> f32 {
+        self.aspect_ratio
+    }
+
+    /// Width / height of the star sprite, the quads of the stars have the same shape
+    pub fn texture_aspect(&self) -> f32 {
+        texture_aspect(self.texture_size)
+    }
+
+    /// The color to clear the window with before the stars are drawn, the stars only draw
+    /// themselves and do not clear the target
+    pub fn set_bg(&mut self, bg: Color) {
+        self.bg = bg;
+    }
+
+    pub fn bg(&self) -> Color {
+        self.bg
+    }
+
+    /// The color that far away stars fade to, black by default
+    ///
+    /// A fog close to the [background](Self::bg) makes far stars blend into it, like in an
+    /// atmosphere.
+    pub fn set_fog_color(&mut self, fog_color: Color) {
+        self.fog_color = fog_color;
+        // the color of all vertices changes
+        self.keyframe = true;
+    }
+
+    pub fn fog_color(&self) -> Color {
+        self.fog_color
+    }
+
+    pub fn set_palette(&mut self, palette: StarPalette) {
+        self.config.palette = palette;
+        // all stars need to get their new color
+        self.keyframe = true;
+    }
+
+    pub fn palette(&self) -> StarPalette {
+        self.config.palette
+    }
+
+    /// Sets how often the vertices of the stars are updated, as pairs of a range in percent of
+    /// all stars, counted from the nearest visible star, and an interval in frames
+    ///
+    /// Updating far stars less often saves CPU time, but makes their movement less smooth. The
+    /// ranges must cover `0..100` without gaps or overlaps, and the intervals must not be `0`.
+    pub fn set_update_tiers(&mut self, mut tiers: Vec<(Range<u8>, u64)>) -> BwgResult<()> {
+        tiers.sort_by_key(|(range, _)| range.start);
+        let mut covered = 0;
+        for (range, interval) in &tiers {
+            if range.start != covered {
+                return Err(anyhow!(
+                    "invalid update tiers: {range:?} should start at {covered}, the ranges \
+                    must not overlap or leave gaps"
+                )
+                .into());
+            }
+            if range.is_empty() {
+                return Err(anyhow!("invalid update tiers: {range:?} is empty").into());
+            }
+            if *interval == 0 {
+                return Err(anyhow!("invalid update tiers: the interval of {range:?} is 0").into());
+            }
+            covered = range.end;
+        }
+        if covered != 100 {
+            return Err(anyhow!(
+                "invalid update tiers: the ranges must cover 0..100, but end at {covered}"
+            )
+            .into());
+        }
+
+        self.update_tiers = tiers;
+        // the ranges that were skipped until now might not be updated for a while
+        self.keyframe = true;
+        Ok(())
+    }
+
+    pub fn update_tiers(&self) -> &[(Range<u8>, u64)] {
+        &self.update_tiers
+    }
+
+    /// [BlendMode::ADD] makes overlapping stars brighter, the default is [BlendMode::ALPHA]
+    pub fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+
+    pub fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    /// Twinkling stars change their brightness over time. Only stars whose vertices are updated
+    /// in a frame twinkle in it, so far away stars twinkle slower.
+    pub fn set_twinkle(&mut self, twinkle: bool) {
+        self.twinkle = twinkle;
+    }
+
+    pub fn twinkle(&self) -> bool {
+        self.twinkle
+    }
+
+    /// Mirrors the sprite of every star horizontally
+    pub fn set_flip_x(&mut self, flip_x: bool) {
+        self.flip_x = flip_x;
+        // the texture coordinates of all vertices change
+        self.keyframe = true;
+    }
+
+    pub fn flip_x(&self) -> bool {
+        self.flip_x
+    }
+
+    /// Mirrors the sprite of every star vertically
+    pub fn set_flip_y(&mut self, flip_y: bool) {
+        self.flip_y = flip_y;
+        self.keyframe = true;
+    }
+
+    pub fn flip_y(&self) -> bool {
+        self.flip_y
+    }
+
+    /// Recycled stars fade in over a few frames instead of popping up at full brightness, which
+    /// is most noticeable at low speeds
+    pub fn set_fade_in(&mut self, fade_in: bool) {
+        self.fade_in = fade_in;
+        self.keyframe = true;
+    }
+
+    pub fn fade_in(&self) -> bool {
+        self.fade_in
+    }
+
+    /// Shows a [minimap](Self::draw_minimap) in the bottom right corner
+    pub fn set_minimap(&mut self, minimap: bool) {
+        self.minimap = minimap;
+    }
+
+    pub fn minimap(&self) -> bool {
+        self.minimap
+    }
+
+    /// Draws every star as a point at its world position, scaled into `rect`
+    ///
+    /// Near stars are white and far ones dark blue. This only reads the stars, the vertices of the
+    /// points are kept between calls so that nothing is allocated per frame.
+    pub fn draw_minimap(&mut self, target: &mut dyn RenderTarget, rect: FloatRect) -> SfResult<()> {
+        let half_extent = self.spawn_area.half_extent;
+        let near = self.config.near_plane;
+        let depth = self.config.far_plane - near;
+        self.minimap_vertices
+            .par_iter_mut()
+            .zip(self.stars.par_iter())
+            .for_each(|(vertex, star)| {
+                if !star.active {
+                    vertex.color = Color::TRANSPARENT;
+                    return;
+                }
+                vertex.position = Vector2f::new(
+                    rect.left
+                        + (star.position.x + half_extent.x) / (2.0 * half_extent.x) * rect.width,
+                    rect.top
+                        + (star.position.y + half_extent.y) / (2.0 * half_extent.y) * rect.height,
+                );
+                let near_ness = 1.0 - ((star.distance - near) / depth).clamp(0.0, 1.0);
+                let c = (near_ness * 255.0) as u8;
+                vertex.color = Color::rgb(c, c, 96 + (near_ness * 159.0) as u8);
+            });
+        self.minimap_buf.update(&self.minimap_vertices, 0)?;
+
+        let frame = [
+            Vertex::with_pos_color(rect.position(), MINIMAP_BG),
+            Vertex::with_pos_color(Vector2f::new(rect.left + rect.width, rect.top), MINIMAP_BG),
+            Vertex::with_pos_color(rect.position() + rect.size(), MINIMAP_BG),
+            Vertex::with_pos_color(Vector2f::new(rect.left, rect.top + rect.height), MINIMAP_BG),
+        ];
+        let states = sfml::graphics::RenderStates::DEFAULT;
+        target.draw_primitives(&frame, PrimitiveType::TRIANGLE_FAN, &states);
+        target.draw(&*self.minimap_buf);
+        Ok(())
+    }
+
+    /// Binds the controls of the stars to other keys
+    pub fn set_keymap(&mut self, keymap: Keymap) {
+        self.keymap = keymap;
+    }
+
+    pub fn keymap(&self) -> &Keymap {
+        &self.keymap
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    pub fn paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Looks in the direction of the mouse: the further the mouse is from the center of the
+    /// screen, the further the camera is moved sideways, up to [MAX_PAN]
+    fn look_at(&mut self, mouse_x: i32, mouse_y: i32) {
+        let half_w = self.video.width as f32 / 2.0;
+        let half_h = self.video.height as f32 / 2.0;
+        let offset = Vector2f::new(
+            ((mouse_x as f32 - half_w) / half_w).clamp(-1.0, 1.0),
+            ((mouse_y as f32 - half_h) / half_h).clamp(-1.0, 1.0),
+        );
+        let pan = offset * MAX_PAN;
+        if pan != self.pan {
+            self.pan = pan;
+            // the pan moves every star on the screen, not only the ones updated next
+            self.keyframe = true;
+        }
+    }
+
     fn find_index_zero_distance(&self) -> (usize, Option<&Star>) {
         self.stars
             .iter()
@@ -458,10 +1546,14 @@ impl Stars {
             .unwrap_or((0, None))
     }
 
-    fn create_star_texture(sprite_path: Option<PathBuf>) -> SfResult<(FBox<Texture>, Color)> {
-        let star_image = match sprite_path {
-            None => Image::from_memory(include_bytes!("../../../resources/star.png"))?,
-            Some(p) => Image::from_file(p.to_str().expect("could not convert path to str"))?,
+    fn create_star_texture(
+        sprite_path: Option<PathBuf>,
+        sprite_memory: Option<&'static [u8]>,
+    ) -> SfResult<(FBox<Texture>, Color)> {
+        let star_image = match (sprite_path, sprite_memory) {
+            (Some(p), _) => Image::from_file(p.to_str().expect("could not convert path to str"))?,
+            (None, Some(data)) => Image::from_memory(data)?,
+            (None, None) => Image::from_memory(include_bytes!("../../../resources/star.png"))?,
         };
 
         let center_x = star_image.size().x / 2;
@@ -476,29 +1568,99 @@ impl Stars {
         Ok((texture, center_color))
     }
 
-    fn star_chunks(&self) -> usize {
-        self.stars.len().div_ceil(rayon::current_num_threads())
-    }
-
     pub fn sort(&mut self, frame: u64) {
+        // farthest first, total_cmp does not panic on NaN like partial_cmp
         self.stars
-            .sort_by(|a, b| b.distance.partial_cmp(&a.distance).unwrap());
+            .par_sort_unstable_by(|a, b| b.distance.total_cmp(&a.distance));
         self.last_sorted_frame = frame;
     }
 
-    fn adjust_speed(&mut self, add_speed: f32, modifier: bool, frame: u64) {
+    /// Puts the [dirty](Self::dirty) stars back into their sorted place
+    ///
+    /// All other stars stay sorted, as they all move by the same amount. That makes this much
+    /// cheaper than a full [sort](Self::sort).
+    fn reinsert_dirty(&mut self) {
+        if self.dirty.is_empty() {
+            return;
+        }
+        self.dirty.sort_unstable();
+        let mut moved: Vec<Star> = self.dirty.iter().map(|&idx| self.stars[idx]).collect();
+
+        let mut dirty = self.dirty.iter().peekable();
+        let mut idx = 0;
+        self.stars.retain(|_| {
+            let keep = dirty.next_if_eq(&&idx).is_none();
+            idx += 1;
+            keep
+        });
+        self.dirty.clear();
+
+        // farthest first, like the stars
+        moved.sort_unstable_by(|a, b| b.distance.total_cmp(&a.distance));
+        // these are ascending, because the moved stars are sorted like the others
+        let positions: Vec<usize> = moved
+            .iter()
+            .map(|star| {
+                self.stars
+                    .partition_point(|other| other.distance > star.distance)
+            })
+            .collect();
+
+        // Make room at the end, then shift the stars behind each insertion point from the back,
+        // so that every star is copied only once
+        let mut end = self.stars.len();
+        self.stars.resize(end + moved.len(), Star::default());
+        for (k, (star, pos)) in moved.into_iter().zip(positions).enumerate().rev() {
+            self.stars.copy_within(pos..end, pos + k + 1);
+            self.stars[pos + k] = star;
+            end = pos;
+        }
+    }
+
+    fn adjust_speed(&mut self, add_speed: f32, modifier: bool) {
         let bounds = DEFAULT_MAX_FPS as f32;
-        self.speed += add_speed * if modifier { 10.0 } else { 1.0 };
-        self.speed = self.speed.clamp(-bounds, bounds);
+        self.target_speed += add_speed * if modifier { 10.0 } else { 1.0 };
+        self.target_speed = self.target_speed.clamp(-bounds, bounds);
+    }
 
-        if self.speed == 0.0 {
-            self.keyframe = true;
-            self.sort(frame);
+    /// Moves the speed a bit closer to the target speed
+    ///
+    /// Once the stars come to a halt, they are sorted, as the fast updates of the moving stars
+    /// leave them a little out of order.
+    fn ease_speed(&mut self, fps_limit: u64, frame: u64) {
+        if self.speed == self.target_speed {
+            return;
+        }
+        // the same easing per second at every frame rate
+        let frames = DEFAULT_MAX_FPS as f32 / fps_limit as f32;
+        let t = 1.0 - (1.0 - self.acceleration).powf(frames);
+        self.speed += (self.target_speed - self.speed) * t;
+
+        if (self.target_speed - self.speed).abs() < SPEED_SETTLE_THRESHOLD {
+            self.speed = self.target_speed;
+            if self.speed == 0.0 {
+                self.keyframe = true;
+                self.sort(frame);
+            }
         }
     }
 
-    fn update_vertex_ranges(&mut self, ranges: &[(usize, usize)]) -> SfResult<()> {
-        let aspect_ratio = self.video.width as f32 / self.video.height as f32;
+    fn update_vertex_ranges(&mut self, ranges: &[(usize, usize)], frame: u64) -> SfResult<()> {
+        let ctx = StarRenderCtx {
+            width: self.video.width,
+            height: self.video.height,
+            texture_size: &self.texture_size,
+            color: &self.texture_color,
+            config: &self.config,
+            palette: self.config.palette,
+            pan: self.pan,
+            twinkle: self.twinkle,
+            fog_color: self.fog_color,
+            flip_x: self.flip_x,
+            flip_y: self.flip_y,
+            fade_in: self.fade_in,
+            frame,
+        };
 
         // Update vertices for each range
         for &(start, end) in ranges {
@@ -510,30 +1672,14 @@ impl Stars {
             let range_size = end - start;
             let chunk_size = range_size.div_ceil(rayon::current_num_threads());
 
-            // Create chunks based on the range
+            // Pair every chunk of stars with the chunk of vertices belonging to it (4 per star),
+            // so that each thread only writes to its own part of the vertex array
             self.stars[start..end]
                 .par_chunks(chunk_size)
-                .enumerate()
-                .for_each(|(chunk_index, chunk)| {
-                    // SAFETY: We're creating a mutable reference to the vector, but using
-                    // it only for specific star's elements based on index
-                    let vertices_ref = unsafe { please_mutable_ref_vec(&self.star_vertices) };
-                    for (i, star) in chunk.iter().enumerate() {
-                        // Calculate the absolute index in the stars array
-                        let absolute_index = start + chunk_index * chunk_size + i;
-
-                        let mut ctx = StarRenderCtx {
-                            width: self.video.width,
-                            height: self.video.height,
-                            vertices: vertices_ref,
-                            index: absolute_index, // Use the absolute index here
-                            texture_size: &self.texture_size,
-                            color: &self.texture_color,
-                            aspect_ratio,
-                            radius: self.radius,
-                        };
-
-                        star.update_vertices(&mut ctx);
+                .zip(self.star_vertices[start * 4..end * 4].par_chunks_mut(chunk_size * 4))
+                .for_each(|(stars, vertices)| {
+                    for (star, quad) in stars.iter().zip(vertices.chunks_exact_mut(4)) {
+                        star.update_vertices(&ctx, quad);
                     }
                 });
 
@@ -561,7 +1707,7 @@ impl Stars {
         let mut ranges_to_update = Vec::new();
 
         // Calculate ranges as before
-        for (range_percent, frame_interval) in UPDATE_TIERS {
+        for (range_percent, frame_interval) in &self.update_tiers {
             let frame_interval: u64 = (*frame_interval as f32
                 * (fps_limit as f32 / DEFAULT_MAX_FPS as f32))
                 .ceil() as u64;
@@ -658,94 +1804,413 @@ impl Stars {
 }
 
 impl<'s> ComprehensiveElement<'s> for Stars {
-    fn update(&mut self, counters: &Counter, _info: &mut Info<'s>) {
-        if counters.frames % 6 == 0 && self.speed != 0.0 {
-            self.sort(counters.frames);
+    fn try_update(&mut self, counters: &Counter, _info: &mut Info<'s>) -> BwgResult<()> {
+        if self.paused {
+            // the stars do not move, but panning or changed settings still need new vertices
+            if self.keyframe {
+                let ranges = self.get_update_ranges(counters.frames, counters.fps_limit, 0);
+                self.update_vertex_ranges(&ranges, counters.frames)?;
+            }
+            return Ok(());
         }
+        self.ease_speed(counters.fps_limit, counters.frames);
 
         // Update all star positions (cheap operation)
-        let chunk_size = self.star_chunks();
         let fps_limit = counters.fps_limit;
-        self.stars.par_chunks_mut(chunk_size).for_each(|chunk| {
-            for star in chunk {
-                star.update(self.speed, self.video.width, self.video.height, fps_limit);
-            }
-        });
+        // the counter has a clock, which can not be shared with the threads
+        let frames = counters.frames;
+        let chunks = self.stars.len().div_ceil(RNG_CHUNK_SIZE) as u64;
+        self.dirty.par_extend(
+            self.stars
+                .par_chunks_mut(RNG_CHUNK_SIZE)
+                .enumerate()
+                .flat_map_iter(|(idx, chunk)| {
+                    // every frame needs other random numbers for the stars that are reset
+                    let mut rng = chunk_rng(self.config.seed, (frames + 1) * chunks + idx as u64);
+                    let mut recycled = Vec::new();
+                    for (i, star) in chunk.iter_mut().enumerate() {
+                        if star.update(
+                            self.speed,
+                            &self.spawn_area,
+                            fps_limit,
+                            &self.config,
+                            &mut rng,
+                        ) {
+                            recycled.push(idx * RNG_CHUNK_SIZE + i);
+                        }
+                    }
+                    recycled
+                }),
+        );
+        self.reinsert_dirty();
 
         let ranges = self.get_update_ranges(
             counters.frames,
             counters.fps_limit,
             self.find_index_zero_distance().0,
         );
-        self.update_vertex_ranges(&ranges).unwrap_or_else(|e| {
-            error!("Error updating vertices: {}", e);
-        });
+        self.update_vertex_ranges(&ranges, counters.frames)?;
+        Ok(())
     }
 
-    fn draw_with(
+    fn draw_to(
         &mut self,
-        sfml_w: &mut FBox<RenderWindow>,
-        _egui_w: &mut bewegrs::egui_sfml::SfEgui,
+        target: &mut dyn RenderTarget,
         _counters: &Counter,
         _info: &mut Info<'s>,
     ) {
         let mut states = sfml::graphics::RenderStates::DEFAULT;
         states.texture = Some(&*self.texture);
-
-        sfml_w.draw_with_renderstates(&*self.star_vertices_buf, &states);
+        // hidden stars are fully transparent, so they add nothing with additive blending either
+        states.blend_mode = self.blend_mode;
+
+        target.draw_with_renderstates(&*self.star_vertices_buf, &states);
+
+        if self.minimap {
+            let size = target.size();
+            let width = size.x as f32 * MINIMAP_SCALE;
+            // same aspect ratio as the area the stars are placed in
+            let height = width * self.spawn_area.half_extent.y / self.spawn_area.half_extent.x;
+            let rect = FloatRect::new(
+                size.x as f32 - width - MINIMAP_MARGIN,
+                size.y as f32 - height - MINIMAP_MARGIN,
+                width,
+                height,
+            );
+            self.draw_minimap(target, rect).unwrap_or_else(|e| {
+                error!("Error drawing the minimap: {}", e);
+            });
+        }
     }
 
     fn z_level(&self) -> u16 {
         0
     }
 
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn handle_messages(&mut self, bus: &EventBus, _counters: &Counter, _info: &mut Info<'s>) {
+        if bus.contains::<ResetStars>() {
+            self.reset();
+        }
+    }
+
     fn update_slow(&mut self, _counters: &Counter, info: &mut Info<'s>) {
         info.set_custom_info("last_sort", self.last_sorted_frame);
+        info.set_custom_info("paused", self.paused);
+        // the speed can also be changed in the egui controls
+        info.set_custom_info("speed", format_args!("{:.03}", self.speed));
+        info.set_custom_info("target_speed", format_args!("{:.03}", self.target_speed));
+        info.set_custom_info("star_r", self.config.radius);
+    }
+
+    fn egui_controls(&mut self, ui: &mut egui::Ui, bus: &mut EventBus) {
+        let bounds = DEFAULT_MAX_FPS as f32;
+        ui.add(egui::Slider::new(&mut self.target_speed, -bounds..=bounds).text("speed"));
+        ui.add(egui::Slider::new(&mut self.acceleration, ACCELERATION_RANGE).text("acceleration"));
+        if ui
+            .add(egui::Slider::new(&mut self.config.radius, 1.0..=1000.0).text("radius"))
+            .changed()
+        {
+            // the radius is used for all vertices, not only the ones updated next
+            self.keyframe = true;
+        }
+        // the distribution is only used when the stars are placed, so this needs a reset
+        egui::ComboBox::from_label("distribution")
+            .selected_text(format!("{:?}", self.config.distribution))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(
+                    &mut self.config.distribution,
+                    SpawnDistribution::Uniform,
+                    "Uniform",
+                );
+                ui.selectable_value(
+                    &mut self.config.distribution,
+                    SpawnDistribution::VolumeWeighted,
+                    "VolumeWeighted",
+                );
+            });
+        ui.checkbox(&mut self.minimap, "minimap");
+        if ui
+            .button(format!("reset ({:?})", self.keymap.reset))
+            .clicked()
+        {
+            bus.post(ResetStars);
+        }
     }
 
-    fn process_event(&mut self, event: &Event, counters: &Counter, info: &mut Info<'s>) {
+    fn process_event(&mut self, event: &Event, _counters: &Counter, info: &mut Info<'s>) {
         match event {
-            Event::KeyPressed {
-                code: Key::W,
-                shift,
-                ..
-            } => {
-                self.adjust_speed(0.1, *shift, counters.fps_limit);
-                info.set_custom_info("speed", format_args!("{:.03}", self.speed));
+            Event::KeyPressed { code, shift, .. } if *code == self.keymap.speed_up => {
+                self.adjust_speed(0.1, *shift);
+                info.set_custom_info("target_speed", format_args!("{:.03}", self.target_speed));
             }
-            Event::KeyPressed {
-                code: Key::S,
-                shift,
-                ..
-            } => {
-                self.adjust_speed(-0.1, *shift, counters.fps_limit);
-                info.set_custom_info("speed", format_args!("{:.03}", self.speed));
+            Event::KeyPressed { code, shift, .. } if *code == self.keymap.speed_down => {
+                self.adjust_speed(-0.1, *shift);
+                info.set_custom_info("target_speed", format_args!("{:.03}", self.target_speed));
             }
             Event::KeyPressed {
-                code: Key::Space,
-                shift: true,
-                ..
-            } => {
+                code, shift: true, ..
+            } if *code == self.keymap.stop => {
+                // stopping is immediate, without easing
                 self.speed = 0.0;
+                self.target_speed = 0.0;
                 info.set_custom_info("speed", format_args!("{:.03}", self.speed));
+                info.set_custom_info("target_speed", format_args!("{:.03}", self.target_speed));
+            }
+            Event::KeyPressed { code, .. } if *code == self.keymap.reset => info.post(ResetStars),
+            Event::KeyPressed { code, .. } if *code == self.keymap.pause => {
+                self.paused = !self.paused;
+                info.set_custom_info("paused", self.paused);
             }
+            Event::MouseMoved { x, y } => self.look_at(*x, *y),
             _ => (),
         }
     }
 }
 
-#[allow(invalid_reference_casting)]
-#[allow(mutable_transmutes)]
-#[allow(clippy::mut_from_ref)]
-#[inline]
-unsafe fn please_mutable_ref<T>(thing: &T) -> &mut T {
-    unsafe { std::mem::transmute(thing) }
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bewegrs::graphic::testing::run_headless;
+
+    fn render_ctx<'r>(
+        width: u32,
+        height: u32,
+        texture_size: &'r Vector2u,
+        config: &'r StarsConfig,
+    ) -> StarRenderCtx<'r> {
+        StarRenderCtx {
+            width,
+            height,
+            texture_size,
+            color: &Color::WHITE,
+            config,
+            palette: StarPalette::Monochrome,
+            pan: Vector2f::new(0.0, 0.0),
+            twinkle: false,
+            fog_color: Color::BLACK,
+            flip_x: false,
+            flip_y: false,
+            fade_in: false,
+            frame: 0,
+        }
+    }
+
+    fn quad_of(star: &Star, ctx: &StarRenderCtx) -> [Vertex; 4] {
+        let mut quad = [Vertex::default(); 4];
+        star.update_vertices(ctx, &mut quad);
+        quad
+    }
+
+    fn center(quad: &[Vertex; 4]) -> Vector2f {
+        quad.iter()
+            .fold(Vector2f::new(0.0, 0.0), |sum, v| sum + v.position)
+            / 4.0
+    }
+
+    #[test]
+    fn stars_are_not_stretched_at_21_to_9() {
+        let config = StarsConfig::default();
+        let texture_size = Vector2u::new(64, 64);
+        let ctx = render_ctx(2560, 1080, &texture_size, &config);
+
+        // twice as far as the near plane, so everything is half as big
+        let star = Star {
+            position: Vector2f::new(1000.0, 500.0),
+            distance: config.near_plane * 2.0,
+            ..Star::new()
+        };
+        let quad = quad_of(&star, &ctx);
+
+        let c = center(&quad);
+        assert!((c.x - (1280.0 + 500.0)).abs() < 0.01, "x = {}", c.x);
+        assert!((c.y - (540.0 + 250.0)).abs() < 0.01, "y = {}", c.y);
+
+        // a square sprite stays square, the aspect ratio of the screen does not matter
+        let width = quad[1].position.x - quad[0].position.x;
+        let height = quad[3].position.y - quad[0].position.y;
+        assert!((width - height).abs() < 0.01, "{width}x{height}");
+        assert!((width - config.radius).abs() < 0.01, "width = {width}");
+    }
+
+    #[test]
+    fn quads_have_the_aspect_of_the_sprite() {
+        let config = StarsConfig::default();
+        let star = Star {
+            distance: config.near_plane,
+            ..Star::new()
+        };
+
+        for (texture_size, aspect) in [(Vector2u::new(128, 64), 2.0), (Vector2u::new(64, 128), 0.5)]
+        {
+            let ctx = render_ctx(1920, 1080, &texture_size, &config);
+            let quad = quad_of(&star, &ctx);
+            // the star has no rotation, so the quad is not rotated yet
+            let width = quad[1].position.x - quad[0].position.x;
+            let height = quad[3].position.y - quad[0].position.y;
+            assert!((width / height - aspect).abs() < 0.001, "{width}x{height}");
+            // the longer side gets the full radius
+            assert!((width.max(height) - config.radius * 2.0).abs() < 0.01);
+        }
+    }
+
+    /// How many of `samples` random distances fall into each tenth between the planes
+    fn distance_histogram(distribution: SpawnDistribution, samples: usize) -> [usize; 10] {
+        let config = StarsConfig {
+            distribution,
+            ..Default::default()
+        };
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut histogram = [0; 10];
+        for _ in 0..samples {
+            let distance = Star::rand_distance(&config, &mut rng);
+            let ratio = projection::depth_ratio(distance, config.near_plane, config.far_plane);
+            assert!(
+                (0.0..=1.0).contains(&ratio),
+                "{distance} is outside of the planes"
+            );
+            histogram[((ratio * 10.0) as usize).min(9)] += 1;
+        }
+        histogram
+    }
+
+    #[test]
+    fn volume_weighted_puts_more_stars_near_the_far_plane() {
+        let uniform = distance_histogram(SpawnDistribution::Uniform, 10_000);
+        let volume = distance_histogram(SpawnDistribution::VolumeWeighted, 10_000);
+
+        // uniform is flat, about 1000 per bucket
+        assert!(
+            uniform.iter().all(|&n| (800..1200).contains(&n)),
+            "{uniform:?}"
+        );
+        // the last tenth holds about 27% of the volume
+        assert!(volume[9] > uniform[9] * 2, "{volume:?} vs {uniform:?}");
+        assert!(volume[0] < uniform[0] / 10, "{volume:?} vs {uniform:?}");
+        // the further away, the more stars
+        assert!(volume.windows(2).all(|w| w[0] <= w[1]), "{volume:?}");
+    }
+
+    fn seeded_field(seed: Option<u64>) -> Vec<Star> {
+        let config = StarsConfig {
+            seed,
+            ..Default::default()
+        };
+        let area = SpawnArea::new(1920, 1080, 1920.0 / 1080.0, &config);
+        // more than one chunk, so the chunks are randomized in parallel
+        let mut stars = vec![Star::new(); RNG_CHUNK_SIZE * 3 + 17];
+        Stars::randomize_stars(&mut stars, &area, &config);
+        stars
+    }
+
+    fn same_star(a: &Star, b: &Star) -> bool {
+        a.position == b.position
+            && a.distance == b.distance
+            && a.rotation == b.rotation
+            && a.rotation_speed == b.rotation_speed
+            && a.twinkle_phase == b.twinkle_phase
+            && a.twinkle_speed == b.twinkle_speed
+    }
+
+    #[test]
+    fn the_same_seed_gives_the_same_stars() {
+        let a = seeded_field(Some(42));
+        let b = seeded_field(Some(42));
+        assert!(a.iter().zip(&b).all(|(a, b)| same_star(a, b)));
+
+        let c = seeded_field(Some(43));
+        assert!(!a.iter().zip(&c).all(|(a, c)| same_star(a, c)));
+    }
+
+    /// A small seeded field, for driving with [run_headless]
+    fn headless_stars(amount: usize) -> Stars {
+        Stars::builder(VideoMode::new(1280, 720, 32))
+            .amount(amount)
+            .seed(Some(1))
+            .build()
+            .expect("could not build the stars")
+    }
+
+    #[test]
+    fn stars_with_a_nan_distance_are_reset() {
+        let config = StarsConfig::default();
+        let area = SpawnArea::new(1920, 1080, 1920.0 / 1080.0, &config);
+        let mut rng = StdRng::seed_from_u64(3);
+
+        let mut star = Star {
+            distance: f32::NAN,
+            ..Star::new()
+        };
+        assert!(star.update(config.speed, &area, DEFAULT_MAX_FPS, &config, &mut rng));
+        assert_eq!(star.distance, config.far_plane);
+
+        // a NaN speed makes the distance NaN in the update itself
+        let mut star = Star {
+            distance: config.far_plane / 2.0,
+            ..Star::new()
+        };
+        assert!(star.update(f32::NAN, &area, DEFAULT_MAX_FPS, &config, &mut rng));
+        assert!(star.distance.is_finite());
+    }
+
+    #[test]
+    fn a_nan_speed_does_not_panic() {
+        let mut stars = headless_stars(1000);
+        stars.speed = f32::NAN;
+        stars.target_speed = f32::NAN;
+
+        run_headless(&mut stars, 30).expect("updating the stars failed");
+
+        assert!(stars.stars.iter().all(|star| star.distance.is_finite()));
+    }
 
-#[allow(invalid_reference_casting)]
-#[allow(clippy::mut_from_ref)]
-#[inline]
-// seems redundant but is important for sized
-unsafe fn please_mutable_ref_vec<T: Sized>(vec: &Vec<T>) -> &mut Vec<T> {
-    unsafe { please_mutable_ref(vec) }
+    #[test]
+    fn recycled_stars_stay_between_the_planes_and_sorted() {
+        let mut stars = headless_stars(5000);
+        // fast enough that every star passes the camera in the run
+        stars.speed = DEFAULT_MAX_FPS as f32;
+        stars.target_speed = stars.speed;
+        let before: Vec<f32> = stars.stars.iter().map(|star| star.distance).collect();
+
+        let info = run_headless(&mut stars, 120).expect("updating the stars failed");
+
+        let config = *stars.config();
+        assert_eq!(stars.stars.len(), 5000);
+        assert!(
+            stars
+                .stars
+                .iter()
+                .all(|star| { (-config.behind_camera..config.far_plane).contains(&star.distance) })
+        );
+        // the recycled stars were put back in their place, farthest first
+        assert!(
+            stars
+                .stars
+                .windows(2)
+                .all(|w| w[0].distance >= w[1].distance)
+        );
+        assert!(
+            stars
+                .stars
+                .iter()
+                .zip(&before)
+                .any(|(star, before)| star.distance != *before)
+        );
+        assert_eq!(info["speed"], format!("{:.03}", stars.speed));
+    }
+
+    #[test]
+    fn only_x_of_the_spawn_area_is_widened() {
+        let config = StarsConfig::default();
+        let area = SpawnArea::new(2560, 1080, 2560.0 / 1080.0, &config);
+        assert!((area.half_extent.x - config.spread * 2560.0 / 1080.0).abs() < 0.1);
+        assert_eq!(area.half_extent.y, config.spread);
+    }
 }
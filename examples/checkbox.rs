@@ -0,0 +1,47 @@
+use bewegrs::{
+    errors::BwgResult,
+    graphic::{
+        nativeui::elements::{NativeElement, checkbox::CheckBox},
+        window::{DEFAULT_MSAA, open_window},
+    },
+    setup,
+};
+use sfml::{
+    graphics::{Color, Font, RenderTarget},
+    window::{Event, Key, Style, VideoMode},
+};
+
+const BG_OFF: Color = Color::rgb(20, 20, 30);
+const BG_ON: Color = Color::rgb(40, 70, 110);
+
+fn main() -> BwgResult<()> {
+    setup(true);
+    let video = VideoMode::desktop_mode();
+    let mut window = open_window(video, "Checkbox", Style::DEFAULT, DEFAULT_MSAA)?;
+
+    let mut font = Font::new()?;
+    font.load_from_memory_static(include_bytes!("../resources/sansation.ttf"))?;
+
+    let mut checkbox = CheckBox::new(40.0).with_label("blue backdrop", &font, 28);
+    checkbox.set_position((video.width as f32 / 2.0 - 120.0, video.height as f32 / 2.0).into());
+
+    'mainloop: loop {
+        while let Some(event) = window.poll_event() {
+            match event {
+                Event::Closed
+                | Event::KeyPressed {
+                    code: Key::Escape, ..
+                } => break 'mainloop,
+                _ => {
+                    checkbox.handle_event(&event, window.mouse_position());
+                }
+            }
+        }
+
+        window.clear(if checkbox.is_checked() { BG_ON } else { BG_OFF });
+        window.draw(&checkbox);
+        window.display();
+    }
+
+    Ok(())
+}
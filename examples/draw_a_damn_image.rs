@@ -1,12 +1,15 @@
+use bewegrs::{
+    errors::BwgResult,
+    graphic::window::{DEFAULT_MSAA, open_window},
+};
 use sfml::{
-    SfResult,
-    graphics::{Image, IntRect, RenderTarget, RenderWindow, Sprite, Texture},
+    graphics::{Image, IntRect, RenderTarget, Sprite, Texture},
     system::{Time, sleep},
     window::{Style, VideoMode},
 };
-fn main() -> SfResult<()> {
+fn main() -> BwgResult<()> {
     let video = VideoMode::desktop_mode();
-    let mut window = RenderWindow::new(video, "Custom shape", Style::DEFAULT, &Default::default())?;
+    let mut window = open_window(video, "Custom shape", Style::DEFAULT, DEFAULT_MSAA)?;
 
     let texture = Texture::from_image(
         &*Image::from_memory(include_bytes!("../resources/logo.png"))?,
@@ -1,9 +1,12 @@
-use bewegrs::{setup, shapes::RectRoundShape};
+use bewegrs::{
+    errors::BwgResult,
+    graphic::window::{DEFAULT_MSAA, open_window},
+    setup,
+    shapes::RectRoundShape,
+};
 use sfml::{
-    SfResult,
     graphics::{
-        CircleShape, Color, CustomShape, CustomShapePoints, RenderTarget, RenderWindow, Shape,
-        Transformable,
+        CircleShape, Color, CustomShape, CustomShapePoints, RenderTarget, Shape, Transformable,
     },
     system::{Time, Vector2f, sleep},
     window::{Event, Key, Style, VideoMode},
@@ -28,10 +31,10 @@ impl CustomShapePoints for TriangleShape {
     }
 }
 
-fn main() -> SfResult<()> {
+fn main() -> BwgResult<()> {
     setup(true);
     let video = VideoMode::desktop_mode();
-    let mut window = RenderWindow::new(video, "Custom shape", Style::DEFAULT, &Default::default())?;
+    let mut window = open_window(video, "Custom shape", Style::DEFAULT, DEFAULT_MSAA)?;
 
     let center: Vector2f = (video.width as f32 / 2.0, video.height as f32 / 2.0).into();
 
@@ -1,9 +1,9 @@
-use rapier2d::prelude::{ColliderBuilder, RigidBody, RigidBodyBuilder};
+use rapier2d::prelude::{RigidBody, RigidBodyBuilder};
 use sfml::{
     SfResult,
     graphics::{
-        CircleShape, Color, CustomShape, CustomShapePoints, Font, RectangleShape, RenderTarget,
-        RenderWindow, Shape, Transformable, glsl::Vec2,
+        CircleShape, Color, CustomShape, CustomShapePoints, FloatRect, Font, RectangleShape,
+        RenderTarget, Shape, Transformable, glsl::Vec2,
     },
     system::Vector2f,
     window::{Event, Key, Style, VideoMode},
@@ -12,8 +12,14 @@ use tracing::info;
 
 use bewegrs::{
     errors::BwgResult,
-    graphic::{ComprehensiveElement, ComprehensiveUi},
-    physics::{PhysicsElement, world::PhysicsWorld2D},
+    graphic::{
+        ComprehensiveElement, ComprehensiveUi,
+        window::{DEFAULT_MSAA, open_window},
+    },
+    physics::{
+        ColliderKind, PhysicsElement,
+        world::{DebugDrawFlags, PhysicsWorld2D},
+    },
     setup,
     shapes::RectRoundShape,
 };
@@ -40,14 +46,17 @@ impl Thing<'_> {
 }
 
 impl<'s> ComprehensiveElement<'s> for Thing<'s> {
-    fn draw_with(
+    fn draw_to(
         &mut self,
-        sfml_w: &mut sfml::cpp::FBox<RenderWindow>,
-        _egui_w: &mut egui_sfml::SfEgui,
+        target: &mut dyn RenderTarget,
         _counters: &bewegrs::counter::Counter,
         _info: &mut bewegrs::graphic::elements::info::Info<'s>,
     ) {
-        sfml_w.draw(&self.shape);
+        target.draw(&self.shape);
+    }
+
+    fn bounds(&self) -> Option<FloatRect> {
+        Some(self.shape.global_bounds())
     }
 }
 
@@ -56,8 +65,11 @@ impl<'s> PhysicsElement<'s> for Thing<'s> {
         RigidBodyBuilder::dynamic().build()
     }
 
-    fn init_collider(&self) -> rapier2d::prelude::Collider {
-        ColliderBuilder::cuboid(Self::X / 2.0, Self::Y / 2.0).build()
+    fn collider_kind(&self) -> ColliderKind {
+        ColliderKind::Cuboid((Self::X / 2.0, Self::Y / 2.0).into())
+    }
+    fn restitution(&self) -> f32 {
+        0.7
     }
     fn set_position(&mut self, position: Vector2f) {
         self.shape.set_position(position);
@@ -65,6 +77,60 @@ impl<'s> PhysicsElement<'s> for Thing<'s> {
     fn get_position(&self) -> Vector2f {
         self.shape.position()
     }
+    fn set_rotation(&mut self, radians: f32) {
+        // the origin is in the center of the shape, so this rotates around the center as well
+        self.shape.set_rotation(radians.to_degrees());
+    }
+}
+
+struct Ball<'s> {
+    shape: CircleShape<'s>,
+}
+
+impl Ball<'_> {
+    const R: f32 = 40.0;
+
+    fn new(position: Vector2f) -> Self {
+        let mut shape = CircleShape::new(Self::R, 32);
+        shape.set_fill_color(Color::BLUE);
+        shape.set_origin((Self::R, Self::R));
+        shape.set_position(position);
+        Self { shape }
+    }
+}
+
+impl<'s> ComprehensiveElement<'s> for Ball<'s> {
+    fn draw_to(
+        &mut self,
+        target: &mut dyn RenderTarget,
+        _counters: &bewegrs::counter::Counter,
+        _info: &mut bewegrs::graphic::elements::info::Info<'s>,
+    ) {
+        target.draw(&self.shape);
+    }
+
+    fn bounds(&self) -> Option<FloatRect> {
+        Some(self.shape.global_bounds())
+    }
+}
+
+impl<'s> PhysicsElement<'s> for Ball<'s> {
+    fn init_rigid_body(&self) -> rapier2d::prelude::RigidBody {
+        RigidBodyBuilder::dynamic().build()
+    }
+
+    fn collider_kind(&self) -> ColliderKind {
+        ColliderKind::Ball(Self::R)
+    }
+    fn set_position(&mut self, position: Vector2f) {
+        self.shape.set_position(position);
+    }
+    fn get_position(&self) -> Vector2f {
+        self.shape.position()
+    }
+    fn set_rotation(&mut self, radians: f32) {
+        self.shape.set_rotation(radians.to_degrees());
+    }
 }
 
 struct Floor<'s> {
@@ -85,14 +151,17 @@ impl Floor<'_> {
 }
 
 impl<'s> ComprehensiveElement<'s> for Floor<'s> {
-    fn draw_with(
+    fn draw_to(
         &mut self,
-        sfml_w: &mut sfml::cpp::FBox<RenderWindow>,
-        _egui_w: &mut egui_sfml::SfEgui,
+        target: &mut dyn RenderTarget,
         _counters: &bewegrs::counter::Counter,
         _info: &mut bewegrs::graphic::elements::info::Info<'s>,
     ) {
-        sfml_w.draw(&self.shape);
+        target.draw(&self.shape);
+    }
+
+    fn bounds(&self) -> Option<FloatRect> {
+        Some(self.shape.global_bounds())
     }
 }
 
@@ -101,8 +170,8 @@ impl<'s> PhysicsElement<'s> for Floor<'s> {
         RigidBodyBuilder::fixed().build()
     }
 
-    fn init_collider(&self) -> rapier2d::prelude::Collider {
-        ColliderBuilder::cuboid(Self::X / 2.0, Self::Y / 2.0).build()
+    fn collider_kind(&self) -> ColliderKind {
+        ColliderKind::Cuboid((Self::X / 2.0, Self::Y / 2.0).into())
     }
     fn set_position(&mut self, position: Vector2f) {
         self.shape.set_position(position);
@@ -117,7 +186,7 @@ fn main() -> BwgResult<()> {
 
     let video = VideoMode::new(1200, 800, 32);
     info!("video mode: {video:?}");
-    let mut window = RenderWindow::new(video, "Drop it!", Style::DEFAULT, &Default::default())?;
+    let mut window = open_window(video, "Drop it!", Style::DEFAULT, DEFAULT_MSAA)?;
 
     let mut font = Font::new()?;
     font.load_from_memory_static(include_bytes!("../resources/sansation.ttf"))?;
@@ -125,14 +194,27 @@ fn main() -> BwgResult<()> {
     let mut gui = ComprehensiveUi::build(&mut window, &font, &video, MAX_FPS)?;
 
     let mut world = PhysicsWorld2D::build()?;
+    // show the colliders and bounds, to see if they match the shapes
+    world.set_debug_draw(DebugDrawFlags::all());
 
     let the_ground = Floor::new();
     let my_box = Thing::new();
+    let my_ball = Ball::new((660.0, 0.0).into());
+    let pendulum = Ball::new((300.0, 350.0).into());
 
-    world.add(Box::new(the_ground));
+    let ground_id = world.add(Box::new(the_ground));
     world.add(Box::new(my_box));
-
-    gui.add(Box::new(world));
+    world.add(Box::new(my_ball));
+    let pendulum_id = world.add(Box::new(pendulum));
+    // hang the pendulum from a point above the left end of the ground, 150 units from its center
+    world.add_revolute_joint(
+        &ground_id,
+        &pendulum_id,
+        (-Floor::X / 2.0, -250.0).into(),
+        (-150.0, 0.0).into(),
+    )?;
+
+    gui.add_named("world", Box::new(world));
 
     'mainloop: loop {
         while let Some(event) = window.poll_event() {
@@ -1,31 +1,35 @@
 use sfml::{
-    SfResult,
     graphics::{
-        CircleShape, Color, CustomShape, Font, RectangleShape, RenderTarget, RenderWindow, Shape,
-        Transformable, glsl::Vec2,
+        CircleShape, Color, CustomShape, Font, RectangleShape, RenderTarget, Shape, Transformable,
+        glsl::Vec2,
     },
-    window::{Event, Key, Style, VideoMode},
+    window::{Event, Key, Style},
 };
 use tracing::info;
 
 use bewegrs::{
-    graphic::ComprehensiveUi,
+    best_video_mode,
+    errors::BwgResult,
+    graphic::{
+        ComprehensiveUi,
+        window::{DEFAULT_MSAA, open_window},
+    },
     setup,
     shapes::{TriangleShape, hue_time},
 };
 
 const MAX_FPS: u64 = 60;
 
-fn main() -> SfResult<()> {
+fn main() -> BwgResult<()> {
     setup(true);
 
-    let video = VideoMode::fullscreen_modes()[0];
+    let video = best_video_mode()?;
     info!("video mode: {video:?}");
-    let mut window = RenderWindow::new(
+    let mut window = open_window(
         video,
         "Custom shape",
         Style::DEFAULT | Style::FULLSCREEN,
-        &Default::default(),
+        DEFAULT_MSAA,
     )?;
 
     let mut font = Font::new()?;
@@ -1,4 +1,5 @@
 use std::fmt::Write;
+use std::time::Duration;
 
 use ringbuffer::RingBuffer as _;
 use sfml::SfResult;
@@ -9,6 +10,9 @@ use crate::errors::BwgResult;
 
 type RingBuffer<T> = ringbuffer::AllocRingBuffer<T>;
 
+/// Time at the end of a frame that [Counter::limit_frame] busy waits instead of sleeping
+pub const LIMIT_SPIN_MS: f32 = 1.0;
+
 /// lazy fields get updated every [Self::fps_limit] frames
 #[derive(Debug)]
 pub struct Counter {
@@ -16,10 +20,15 @@ pub struct Counter {
     pub frames: u64,
     /// frame counter lazy
     pub l_frames: u64,
-    /// seconds counter
+    /// seconds counter, the time at the start of the current frame
+    ///
+    /// Use this for animations and simulations, it is the same for everything in a frame. For
+    /// the actual time that passed until now, see [Self::real_seconds].
     pub seconds: f32,
     /// seconds counter lazy
     pub l_seconds: f32,
+    /// seconds between the start of the last frame and the start of this frame
+    pub dt: f32,
     pub frame_time_pre: f32,
     pub frame_times: RingBuffer<f32>,
     /// actually keeps track of time
@@ -40,6 +49,7 @@ impl Counter {
             frames: 0,
             seconds: 0.0,
             l_seconds: 0.0,
+            dt: 0.0,
             frame_time_pre: 0.0,
             frame_times: RingBuffer::new(fps_limit as usize),
             text: String::new(),
@@ -60,7 +70,7 @@ impl Counter {
             .expect("could not write to text buffer");
         writeln!(self.text, "frames: {}", self.frames).expect("could not write to text buffer");
 
-        writeln!(self.text, "FPS: {:02.1}", self.fps().round())
+        writeln!(self.text, "FPS: {:02.1}", self.smoothed_fps().round())
             .expect("could not write to text buffer");
         write!(
             self.text,
@@ -72,7 +82,9 @@ impl Counter {
     }
 
     pub fn frame_start(&mut self) {
-        self.seconds = self.clock.elapsed_time().as_seconds();
+        let now = self.clock.elapsed_time().as_seconds();
+        self.dt = now - self.seconds;
+        self.seconds = now;
         self.frames += 1;
 
         if self.frames % self.fps_limit == 0 || self.frames == 1 {
@@ -82,6 +94,14 @@ impl Counter {
         }
     }
 
+    /// Seconds since the counter was started, read from the [clock](Self::clock) right now
+    ///
+    /// Unlike [Self::seconds], this keeps changing during a frame and is not tied to the frames
+    /// at all, which is what a wall clock wants.
+    pub fn real_seconds(&self) -> f32 {
+        self.clock.elapsed_time().as_seconds()
+    }
+
     pub fn dframes(&self) -> u64 {
         self.frames - self.l_frames
     }
@@ -98,12 +118,120 @@ impl Counter {
         self.dframes() as f32 / dseconds
     }
 
+    /// Like [Self::fps], but averaged over the recent [frame times](Self::frame_times), so it
+    /// jumps around less
+    ///
+    /// Falls back to [Self::fps] before the first frame time is known.
+    pub fn smoothed_fps(&self) -> f32 {
+        let frame_time = self.a_frame_time();
+        if self.frame_times.is_empty() || frame_time <= 0.0 {
+            return self.fps();
+        }
+        1000.0 / frame_time
+    }
+
     pub fn a_frame_time(&self) -> f32 {
         self.frame_times.iter().sum::<f32>() / self.frame_times.len() as f32
     }
 
+    /// The frame time in ms that 99% of the recent [frame times](Self::frame_times) are below
+    ///
+    /// Spikes show up here much more than in [Self::a_frame_time]. This is `0.0` before the
+    /// first frame time is known.
+    pub fn p99_frame_time(&self) -> f32 {
+        let mut times: Vec<f32> = self.frame_times.iter().copied().collect();
+        if times.is_empty() {
+            return 0.0;
+        }
+        times.sort_unstable_by(f32::total_cmp);
+        let index = ((times.len() as f32 * 0.99).ceil() as usize).saturating_sub(1);
+        times[index]
+    }
+
+    /// A one line summary of the whole run, meant to be logged at shutdown
+    ///
+    /// The average is over all frames, the p99 only over the recent ones, see
+    /// [Self::p99_frame_time].
+    pub fn summary(&self) -> String {
+        let avg = if self.frames == 0 {
+            0.0
+        } else {
+            self.seconds * 1000.0 / self.frames as f32
+        };
+        format!(
+            "{} frames in {:.2}s ({:.3}ms per frame, p99 {:.3}ms)",
+            self.frames,
+            self.seconds,
+            avg,
+            self.p99_frame_time()
+        )
+    }
+
+    /// Waits until [Self::ms_per_frame] have passed since [Self::frame_start]
+    ///
+    /// This is an alternative to the frame limit of SFML, which only sleeps with a coarse
+    /// granularity. Most of the time is slept, the last [LIMIT_SPIN_MS] are spent busy waiting to
+    /// hit the target more exactly. Disable the SFML limit with
+    /// `window.set_framerate_limit(0)` when using this.
+    pub fn limit_frame(&mut self) {
+        let frame_end = self.seconds + self.ms_per_frame() / 1000.0;
+        let remaining = frame_end - self.clock.elapsed_time().as_seconds();
+        let sleep = remaining - LIMIT_SPIN_MS / 1000.0;
+        if sleep > 0.0 {
+            std::thread::sleep(Duration::from_secs_f32(sleep));
+        }
+        while self.clock.elapsed_time().as_seconds() < frame_end {
+            std::hint::spin_loop();
+        }
+    }
+
     pub fn frame_prepare_display(&mut self) {
         self.frame_times
             .push((self.clock.elapsed_time().as_seconds() - self.seconds) * 1000.0);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steady_frames_give_60_fps() {
+        let mut c = Counter::start(60).expect("could not start the counter");
+        // the second second of a run at 16.6ms per frame, like frame_start would leave it
+        c.l_frames = 60;
+        c.l_seconds = 60.0 * 0.0166;
+        c.frames = 120;
+        c.seconds = 120.0 * 0.0166;
+        for _ in 0..60 {
+            c.frame_times.push(16.6);
+        }
+
+        assert!((c.fps() - 60.0).abs() < 0.5, "fps = {}", c.fps());
+        assert!(
+            (c.smoothed_fps() - 60.0).abs() < 0.5,
+            "smoothed fps = {}",
+            c.smoothed_fps()
+        );
+    }
+
+    #[test]
+    fn summary_of_synthetic_frames() {
+        let mut c = Counter::start(60).expect("could not start the counter");
+        assert_eq!(
+            c.summary(),
+            "0 frames in 0.00s (0.000ms per frame, p99 0.000ms)"
+        );
+
+        c.frames = 120;
+        c.seconds = 2.0;
+        for _ in 0..59 {
+            c.frame_times.push(10.0);
+        }
+        c.frame_times.push(30.0);
+        assert_eq!(
+            c.summary(),
+            "120 frames in 2.00s (16.667ms per frame, p99 30.000ms)"
+        );
+    }
+}
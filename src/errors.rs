@@ -6,6 +6,10 @@ pub type BwgResult<T> = std::result::Result<T, BwgError>;
 pub enum BwgError {
     #[error("error with some sfml operation: {0}")]
     Sfml(#[from] sfml::SfError),
+    #[error("there is no element with the id {0:032x}")]
+    ElementNotFound(u128),
+    #[error("error with the physics: {0}")]
+    Physics(String),
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
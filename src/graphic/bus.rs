@@ -0,0 +1,76 @@
+//! A minimal typed message bus, so that elements can react to things without the app wiring
+//! them together by hand
+//!
+//! Messages are posted by the app with [ComprehensiveUi::post](super::ComprehensiveUi::post), or
+//! by elements with [Info::post](super::elements::info::Info::post) and in
+//! [ComprehensiveElement::egui_controls](super::ComprehensiveElement::egui_controls). They are
+//! handed to every element in
+//! [ComprehensiveElement::handle_messages](super::ComprehensiveElement::handle_messages) on the
+//! next update, and dropped afterwards.
+
+use std::any::Any;
+
+/// Messages of any `'static` type for the current frame
+#[derive(Default)]
+pub struct EventBus {
+    messages: Vec<Box<dyn Any>>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a message, it is delivered on the next update
+    pub fn post<T: Any>(&mut self, msg: T) {
+        self.messages.push(Box::new(msg));
+    }
+
+    /// All messages of type `T`, in the order they were posted
+    pub fn read<T: Any>(&self) -> impl Iterator<Item = &T> {
+        self.messages.iter().filter_map(|msg| msg.downcast_ref())
+    }
+
+    /// Whether at least one message of type `T` was posted
+    pub fn contains<T: Any>(&self) -> bool {
+        self.read::<T>().next().is_some()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Moves all messages of `other` behind the messages of this bus, `other` is empty afterwards
+    pub fn append(&mut self, other: &mut EventBus) {
+        self.messages.append(&mut other.messages);
+    }
+
+    /// Drops all messages, done after they were delivered
+    pub fn clear(&mut self) {
+        self.messages.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn append_keeps_the_order_and_empties_the_other_bus() {
+        let mut bus = EventBus::new();
+        bus.post(1u32);
+        let mut outbox = EventBus::new();
+        outbox.post(2u32);
+        outbox.post("not a number");
+
+        bus.append(&mut outbox);
+        assert!(outbox.is_empty());
+        assert_eq!(bus.len(), 3);
+        assert_eq!(bus.read::<u32>().copied().collect::<Vec<_>>(), [1, 2]);
+        assert!(bus.contains::<&str>());
+    }
+}
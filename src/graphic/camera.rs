@@ -0,0 +1,137 @@
+use sfml::cpp::FBox;
+use sfml::graphics::{FloatRect, RenderTarget, View};
+use sfml::system::Vector2f;
+
+/// A camera over the world, wrapping a SFML [View] that can be panned and zoomed
+///
+/// A zoom of `2.0` shows half of the world extent, `0.5` shows double of it.
+pub struct Camera {
+    view: FBox<View>,
+    /// view that maps 1:1 to the screen, for things like the HUD
+    screen: FBox<View>,
+    size: Vector2f,
+    zoom: f32,
+}
+
+impl Camera {
+    pub const DEFAULT_ZOOM: f32 = 1.0;
+
+    /// Creates a camera that shows exactly the area of a screen with `size`
+    pub fn new(size: impl Into<Vector2f>) -> Self {
+        let size: Vector2f = size.into();
+        let center = size / 2.0;
+        Self {
+            view: View::with_center_and_size(center, size),
+            screen: View::with_center_and_size(center, size),
+            size,
+            zoom: Self::DEFAULT_ZOOM,
+        }
+    }
+
+    pub fn set_center(&mut self, center: impl Into<Vector2f>) {
+        self.view.set_center(center);
+    }
+
+    pub fn center(&self) -> Vector2f {
+        self.view.center()
+    }
+
+    pub fn pan(&mut self, offset: impl Into<Vector2f>) {
+        self.view.move_(offset);
+    }
+
+    /// Sets the zoom, relative to the screen size. Non-positive values are ignored.
+    pub fn set_zoom(&mut self, zoom: f32) {
+        if zoom <= 0.0 {
+            return;
+        }
+        self.zoom = zoom;
+        self.view.set_size(self.size / zoom);
+    }
+
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    /// The size of the world area that is currently visible
+    pub fn visible_size(&self) -> Vector2f {
+        self.view.size()
+    }
+
+    /// Centers the camera on the screen again and resets the zoom
+    pub fn reset(&mut self) {
+        self.zoom = Self::DEFAULT_ZOOM;
+        self.view.set_center(self.size / 2.0);
+        self.view.set_size(self.size);
+    }
+
+    /// Adapts the camera to a new screen size, for example after the window was resized
+    ///
+    /// The center and zoom stay the same, so more or less of the world is visible.
+    pub fn resize(&mut self, size: impl Into<Vector2f>) {
+        self.size = size.into();
+        self.view.set_size(self.size / self.zoom);
+        self.screen.set_center(self.size / 2.0);
+        self.screen.set_size(self.size);
+    }
+
+    pub fn size(&self) -> Vector2f {
+        self.size
+    }
+
+    /// The area of the world that is currently visible, in world coordinates
+    pub fn visible_rect(&self) -> FloatRect {
+        let size = self.view.size();
+        let center = self.view.center();
+        FloatRect::from_vecs(center - size / 2.0, size)
+    }
+
+    pub fn view(&self) -> &View {
+        &self.view
+    }
+
+    /// Draw everything after this through the camera
+    pub fn apply<T: RenderTarget + ?Sized>(&self, target: &mut T) {
+        target.set_view(&self.view);
+    }
+
+    /// Draw everything after this in screen coordinates, ignoring the camera
+    pub fn apply_screen<T: RenderTarget + ?Sized>(&self, target: &mut T) {
+        target.set_view(&self.screen);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zoom_2_halves_the_visible_rect() {
+        let mut camera = Camera::new((800.0, 600.0));
+        assert_eq!(
+            camera.visible_rect(),
+            FloatRect::new(0.0, 0.0, 800.0, 600.0)
+        );
+
+        camera.set_zoom(2.0);
+        // the center stays where it is
+        assert_eq!(
+            camera.visible_rect(),
+            FloatRect::new(200.0, 150.0, 400.0, 300.0)
+        );
+        assert_eq!(camera.visible_size(), Vector2f::new(400.0, 300.0));
+
+        camera.set_zoom(0.5);
+        assert_eq!(camera.visible_size(), Vector2f::new(1600.0, 1200.0));
+    }
+
+    #[test]
+    fn non_positive_zoom_is_ignored() {
+        let mut camera = Camera::new((800.0, 600.0));
+        camera.set_zoom(2.0);
+        camera.set_zoom(0.0);
+        camera.set_zoom(-1.0);
+        assert_eq!(camera.zoom(), 2.0);
+        assert_eq!(camera.visible_size(), Vector2f::new(400.0, 300.0));
+    }
+}
@@ -1 +1,3 @@
+pub mod help;
 pub mod info;
+pub mod reticle;
@@ -0,0 +1,135 @@
+use sfml::cpp::FBox;
+use sfml::graphics::{Color, Font, RectangleShape, RenderTarget, Shape, Text, Transformable};
+use sfml::system::{Vector2f, Vector2u};
+use sfml::window::{Event, Key};
+
+use crate::counter::Counter;
+use crate::graphic::{ComprehensiveElement, UI_Z_LEVEL};
+
+use super::info::Info;
+
+/// A panel in the middle of the screen that lists the key bindings, shown and hidden with
+/// [HelpOverlay::TOGGLE_KEY]
+///
+/// It is hidden at first.
+pub struct HelpOverlay<'s> {
+    /// left column, the keys
+    keys: Text<'s>,
+    /// right column, what the keys do
+    actions: Text<'s>,
+    backdrop: RectangleShape<'static>,
+    shown: bool,
+    /// screen size the panel was placed for
+    screen: Vector2u,
+}
+
+impl<'s> HelpOverlay<'s> {
+    pub const TOGGLE_KEY: Key = Key::H;
+    pub const DEFAULT_BACKDROP_COLOR: Color = Color::rgba(10, 10, 20, 200);
+    pub const DEFAULT_FONT_SIZE: u32 = 20;
+    /// space between the text and the edge of the backdrop
+    const PADDING: f32 = 16.0;
+    /// horizontal space between the keys and the actions
+    const COLUMN_GAP: f32 = 32.0;
+
+    /// Lists `bindings` as `(key, action)`, the binding for [Self::TOGGLE_KEY] is added at the
+    /// end
+    pub fn new(font: &'s FBox<Font>, bindings: Vec<(String, String)>) -> Self {
+        let mut keys = String::new();
+        let mut actions = String::new();
+        let toggle = (
+            format!("{:?}", Self::TOGGLE_KEY),
+            "show or hide this help".to_string(),
+        );
+        for (key, action) in bindings.iter().chain([&toggle]) {
+            keys.push_str(key);
+            keys.push('\n');
+            actions.push_str(action);
+            actions.push('\n');
+        }
+
+        let text = |content: &str| {
+            let mut text = Text::new(content, font, Self::DEFAULT_FONT_SIZE);
+            text.set_fill_color(Info::DEFAULT_TEXT_COLOR);
+            text
+        };
+        let mut backdrop = RectangleShape::new();
+        backdrop.set_fill_color(Self::DEFAULT_BACKDROP_COLOR);
+
+        Self {
+            keys: text(keys.trim_end()),
+            actions: text(actions.trim_end()),
+            backdrop,
+            shown: false,
+            // placed on the first update
+            screen: Vector2u::new(0, 0),
+        }
+    }
+
+    pub fn set_shown(&mut self, shown: bool) {
+        self.shown = shown;
+    }
+
+    pub fn shown(&self) -> bool {
+        self.shown
+    }
+
+    /// Centers the panel on the screen
+    fn update_position(&mut self) {
+        let keys = self.keys.local_bounds();
+        let actions = self.actions.local_bounds();
+        let keys_width = keys.left + keys.width;
+        let content = Vector2f::new(
+            keys_width + Self::COLUMN_GAP + actions.left + actions.width,
+            (keys.top + keys.height).max(actions.top + actions.height),
+        );
+        let size = content + Vector2f::new(Self::PADDING, Self::PADDING) * 2.0;
+        let corner = Vector2f::new(
+            (self.screen.x as f32 - size.x) / 2.0,
+            (self.screen.y as f32 - size.y) / 2.0,
+        );
+
+        self.backdrop.set_size(size);
+        self.backdrop.set_position(corner);
+        let text_corner = corner + Vector2f::new(Self::PADDING, Self::PADDING);
+        self.keys.set_position(text_corner);
+        self.actions
+            .set_position(text_corner + Vector2f::new(keys_width + Self::COLUMN_GAP, 0.0));
+    }
+}
+
+impl<'s> ComprehensiveElement<'s> for HelpOverlay<'s> {
+    fn z_level(&self) -> u16 {
+        UI_Z_LEVEL
+    }
+
+    fn visible(&self) -> bool {
+        self.shown
+    }
+
+    fn update(&mut self, _counters: &Counter, info: &mut Info<'s>) {
+        if info.size() != self.screen {
+            self.screen = info.size();
+            self.update_position();
+        }
+    }
+
+    fn draw_to(
+        &mut self,
+        target: &mut dyn RenderTarget,
+        _counters: &Counter,
+        _info: &mut Info<'s>,
+    ) {
+        target.draw(&self.backdrop);
+        target.draw(&self.keys);
+        target.draw(&self.actions);
+    }
+
+    fn process_event(&mut self, event: &Event, _counters: &Counter, _info: &mut Info<'s>) {
+        if let Event::KeyPressed { code, .. } = event
+            && *code == Self::TOGGLE_KEY
+        {
+            self.shown = !self.shown;
+        }
+    }
+}
@@ -1,65 +1,246 @@
-use std::collections::HashMap;
+use std::any::Any;
 use std::fmt::{Display, Write};
 
 use egui_sfml::{DrawInput, SfEgui};
+use ringbuffer::RingBuffer as _;
 use sfml::cpp::FBox;
 use sfml::graphics::{
-    Color, Font, RenderTarget, RenderWindow, Sprite, Text, Texture, Transformable,
+    Color, Font, PrimitiveType, RenderTarget, RenderWindow, Sprite, Text, Texture, Transformable,
+    Vertex, VertexBuffer, VertexBufferUsage,
 };
-use sfml::system::Vector2f;
+use sfml::system::{Vector2f, Vector2u};
 use sfml::window::{Key, VideoMode};
 use tracing::{debug, error};
 
 use crate::counter::Counter;
 use crate::errors::BwgResult;
+use crate::graphic::bus::EventBus;
 
 #[derive(Default)]
 pub enum InfoKind {
     Egui,
     #[default]
     Overlay,
+    /// the overlay with a graph of the recent frame times below it
+    Graph,
     None,
 }
 
 impl InfoKind {
     fn next(&mut self) {
         *self = match self {
-            Self::Overlay => Self::None,
+            Self::Overlay => Self::Graph,
+            Self::Graph => Self::None,
             Self::None => Self::Egui,
             Self::Egui => Self::Overlay,
         };
     }
 }
 
+/// The screen corner that the overlay is placed in
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverlayAnchor {
+    #[default]
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl OverlayAnchor {
+    fn is_right(self) -> bool {
+        matches!(self, Self::TopRight | Self::BottomRight)
+    }
+
+    fn is_bottom(self) -> bool {
+        matches!(self, Self::BottomLeft | Self::BottomRight)
+    }
+}
+
+/// A group of custom info entries, kept in the order they were added
+struct InfoSection {
+    /// the first section has no name and no header
+    name: Option<String>,
+    entries: Vec<(String, String)>,
+}
+
+impl InfoSection {
+    fn new(name: Option<String>) -> Self {
+        Self {
+            name,
+            entries: Vec::new(),
+        }
+    }
+}
+
 pub struct Info<'s> {
     kind: InfoKind,
+    /// the first column of the overlay
     overlay: Text<'s>,
-    custom_info: HashMap<String, String>,
+    /// further columns of the overlay, when there are more than [Self::max_rows] lines
+    columns: Vec<Text<'s>>,
+    /// lines per column of the overlay
+    max_rows: usize,
+    custom_info: Vec<InfoSection>,
+    /// index of the section that new keys are added to
+    current_section: usize,
     logo: Option<Sprite<'s>>,
     logo_text: Option<Text<'s>>,
-    video: &'s VideoMode,
+    /// mode of the screen, the overlay and logo are placed relative to its size
+    video: VideoMode,
+    /// lines of the frame time graph, two vertices per frame
+    graph: Vec<Vertex>,
+    /// the [graph](Self::graph) on the gpu, made when the graph is first shown
+    graph_buf: Option<FBox<VertexBuffer>>,
+    /// the frame the graph was built for, it only changes when there is a new frame time
+    graph_frame: Option<u64>,
+    anchor: OverlayAnchor,
+    /// title of the egui window
+    egui_title: String,
+    /// fixed size of the egui window, it still grows with its content
+    egui_size: egui::Vec2,
+    /// messages the elements posted, see [Self::post]
+    outbox: EventBus,
 }
 
 impl<'s> Info<'s> {
     pub const DEFAULT_NAME: &'static str = "Info";
+    pub const DEFAULT_EGUI_SIZE: egui::Vec2 = egui::vec2(300.0, 12.0);
+    pub const GRAPH_WIDTH: f32 = 200.0;
+    pub const GRAPH_HEIGHT: f32 = 60.0;
+    const GRAPH_MARGIN: f32 = 8.0;
+    pub const DEFAULT_TEXT_COLOR: Color = Color::rgb(200, 200, 200);
+    pub const DEFAULT_OUTLINE_COLOR: Color = Color::rgb(20, 20, 20);
+    pub const DEFAULT_FONT_SIZE: u32 = 17;
+    const LOGO_TEXT_SIZE: u32 = 13;
+    /// horizontal space between the columns of the overlay
+    const COLUMN_GAP: f32 = 24.0;
 
-    pub fn new(font: &'s FBox<Font>, video: &'s VideoMode, counters: &Counter) -> Self {
-        let mut overlay = Text::new(&counters.text, font, 17);
+    pub fn new(font: &'s FBox<Font>, video: &VideoMode, counters: &Counter) -> Self {
+        let mut overlay = Text::new(&counters.text, font, Self::DEFAULT_FONT_SIZE);
         debug!("info bounds: {:?}", overlay.global_bounds());
-        overlay.set_fill_color(Color::rgb(200, 200, 200));
-        overlay.set_outline_color(Color::rgb(20, 20, 20));
+        overlay.set_fill_color(Self::DEFAULT_TEXT_COLOR);
+        overlay.set_outline_color(Self::DEFAULT_OUTLINE_COLOR);
         overlay.set_outline_thickness(1.0);
-        overlay.set_position(Vector2f::new(
-            video.width as f32 * 0.005,
-            video.height as f32 * 0.005,
-        ));
-        Self {
+        let mut info = Self {
             kind: Default::default(),
             overlay,
-            custom_info: HashMap::new(),
+            columns: Vec::new(),
+            max_rows: usize::MAX,
+            custom_info: vec![InfoSection::new(None)],
+            current_section: 0,
             logo: None,
             logo_text: None,
-            video,
+            video: *video,
+            graph: Vec::new(),
+            graph_buf: None,
+            graph_frame: None,
+            anchor: OverlayAnchor::default(),
+            egui_title: Self::DEFAULT_NAME.to_string(),
+            egui_size: Self::DEFAULT_EGUI_SIZE,
+            outbox: EventBus::new(),
+        };
+        info.update_overlay_position();
+        info
+    }
+
+    pub fn with_text_color(mut self, color: Color) -> Self {
+        self.set_text_color(color);
+        self
+    }
+
+    pub fn with_outline_color(mut self, color: Color) -> Self {
+        self.set_outline_color(color);
+        self
+    }
+
+    pub fn with_font_size(mut self, size: u32) -> Self {
+        self.set_font_size(size);
+        self
+    }
+
+    pub fn with_max_rows(mut self, max_rows: usize) -> Self {
+        self.set_max_rows(max_rows);
+        self
+    }
+
+    pub fn set_text_color(&mut self, color: Color) {
+        for text in self.texts_mut() {
+            text.set_fill_color(color);
+        }
+    }
+
+    pub fn set_outline_color(&mut self, color: Color) {
+        for text in self.texts_mut() {
+            text.set_outline_color(color);
+        }
+    }
+
+    pub fn set_font_size(&mut self, size: u32) {
+        for text in self.texts_mut() {
+            text.set_character_size(size);
+        }
+        self.update_overlay_position();
+    }
+
+    /// When the overlay has more lines than this, it continues in another column next to it.
+    /// By default, there is only one column.
+    pub fn set_max_rows(&mut self, max_rows: usize) {
+        // a column needs at least one line
+        self.max_rows = max_rows.max(1);
+    }
+
+    pub fn max_rows(&self) -> usize {
+        self.max_rows
+    }
+
+    /// All columns of the overlay, from left to right
+    fn texts_mut(&mut self) -> impl Iterator<Item = &mut Text<'s>> {
+        std::iter::once(&mut self.overlay).chain(self.columns.iter_mut())
+    }
+
+    pub fn set_anchor(&mut self, anchor: OverlayAnchor) {
+        self.anchor = anchor;
+        self.update_overlay_position();
+    }
+
+    pub fn anchor(&self) -> OverlayAnchor {
+        self.anchor
+    }
+
+    /// Places the overlay in the corner of the [OverlayAnchor], needs to be called again when the
+    /// size of the text changes
+    ///
+    /// The columns are placed next to each other and treated as one block.
+    fn update_overlay_position(&mut self) {
+        let (size, anchor) = (self.size(), self.anchor);
+        let inset_x = size.x as f32 * 0.005;
+        let inset_y = size.y as f32 * 0.005;
+
+        let mut width = 0.0;
+        let mut height: f32 = 0.0;
+        for text in self.texts_mut() {
+            let bounds = text.local_bounds();
+            width += bounds.left + bounds.width + Self::COLUMN_GAP;
+            height = height.max(bounds.top + bounds.height);
+        }
+        // there is no gap after the last column
+        width -= Self::COLUMN_GAP;
+
+        let mut x = if anchor.is_right() {
+            size.x as f32 - inset_x - width
+        } else {
+            inset_x
+        };
+        let y = if anchor.is_bottom() {
+            size.y as f32 - inset_y - height
+        } else {
+            inset_y
+        };
+        for text in self.texts_mut() {
+            text.set_position(Vector2f::new(x, y));
+            let bounds = text.local_bounds();
+            x += bounds.left + bounds.width + Self::COLUMN_GAP;
         }
     }
 
@@ -73,50 +254,157 @@ impl<'s> Info<'s> {
         let scale = 1.0 / ((logo_rect.width + logo_rect.height) as f32 / 100.0);
         debug!("logo_rect: {logo_rect:?}");
 
-        const LOGO_TEXT_SIZE: u32 = 13;
-
-        let mut logo_text = Text::new(
+        let logo_text = Text::new(
             &logo_text.to_string(),
             self.overlay
                 .font()
                 .expect("could not get font for logo_text"),
-            LOGO_TEXT_SIZE,
+            Self::LOGO_TEXT_SIZE,
         );
 
         logo.set_scale(scale);
+        logo.set_origin((logo_rect.width as f32, logo_rect.height as f32));
+
+        self.logo = Some(logo);
+        self.logo_text = Some(logo_text);
+        self.update_logo_position();
+        Ok(())
+    }
+
+    /// Places the logo in the bottom left corner of the screen
+    fn update_logo_position(&mut self) {
+        let height = self.video.height as f32;
+        let (Some(logo), Some(logo_text)) = (&mut self.logo, &mut self.logo_text) else {
+            return;
+        };
+        let logo_rect = logo.texture_rect();
+        let scale = logo.get_scale().x;
+
         logo.set_position((
             logo_rect.width as f32 * scale + 10.0,
-            self.video.height as f32 - (logo_rect.height as f32 * scale),
+            height - (logo_rect.height as f32 * scale),
         ));
         logo_text.set_position((
             1.3 * logo_rect.width as f32 * scale + 10.0,
-            self.video.height as f32
-                - (logo_rect.height as f32 * scale)
-                - LOGO_TEXT_SIZE as f32 * 2.5,
+            height - (logo_rect.height as f32 * scale) - Self::LOGO_TEXT_SIZE as f32 * 2.5,
         ));
-        logo.set_origin((logo_rect.width as f32, logo_rect.height as f32));
+    }
 
-        self.logo = Some(logo);
-        self.logo_text = Some(logo_text);
-        Ok(())
+    /// Sets the size of the screen, for example after the window was resized
+    pub fn set_size(&mut self, size: Vector2u) {
+        self.video.width = size.x;
+        self.video.height = size.y;
+        self.update_overlay_position();
+        self.update_logo_position();
+    }
+
+    pub fn size(&self) -> Vector2u {
+        Vector2u::new(self.video.width, self.video.height)
+    }
+
+    /// The video mode the info was made for, with the size of the last [Self::set_size]
+    pub fn video(&self) -> VideoMode {
+        self.video
     }
 
+    /// Sets the value for a key. Keys that already exist keep their place, new keys are added to
+    /// the end of the current section, see [Self::set_section].
     pub fn set_custom_info(&mut self, key: impl Display, value: impl Display) {
-        self.custom_info.insert(key.to_string(), value.to_string());
+        let key = key.to_string();
+        let value = value.to_string();
+        for section in self.custom_info.iter_mut() {
+            if let Some(entry) = section.entries.iter_mut().find(|(k, _)| *k == key) {
+                entry.1 = value;
+                return;
+            }
+        }
+        self.custom_info[self.current_section]
+            .entries
+            .push((key, value));
+    }
+
+    /// All custom info entries, section by section
+    pub fn custom_info(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.custom_info
+            .iter()
+            .flat_map(|section| section.entries.iter())
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Removes a key, if it exists
+    pub fn remove_custom_info(&mut self, key: impl Display) {
+        let key = key.to_string();
+        for section in self.custom_info.iter_mut() {
+            section.entries.retain(|(k, _)| *k != key);
+        }
+    }
+
+    /// Removes all keys and sections
+    pub fn clear_custom_info(&mut self) {
+        self.custom_info = vec![InfoSection::new(None)];
+        self.current_section = 0;
     }
 
+    /// Keys that are added after this are grouped under a header with the name of the section.
+    /// Sections are shown in the order they were first set.
+    pub fn set_section(&mut self, section: &str) {
+        self.current_section = match self
+            .custom_info
+            .iter()
+            .position(|s| s.name.as_deref() == Some(section))
+        {
+            Some(idx) => idx,
+            None => {
+                self.custom_info
+                    .push(InfoSection::new(Some(section.to_string())));
+                self.custom_info.len() - 1
+            }
+        };
+    }
+
+    /// Sends a message to all elements, like
+    /// [ComprehensiveUi::post](crate::graphic::ComprehensiveUi::post) does for the app
+    ///
+    /// This lets elements talk to each other: anything they post while they update, handle
+    /// events or messages is delivered to
+    /// [handle_messages](crate::graphic::ComprehensiveElement::handle_messages) on the next
+    /// update.
+    pub fn post<T: Any>(&mut self, msg: T) {
+        self.outbox.post(msg);
+    }
+
+    /// The messages that were [posted](Self::post) since the last update
+    pub(crate) fn outbox_mut(&mut self) -> &mut EventBus {
+        &mut self.outbox
+    }
+
+    /// Runs the egui pass of the info window
+    ///
+    /// With [InfoKind::Egui], `controls` is called to add widgets below the text, for example the
+    /// [egui_controls](crate::graphic::ComprehensiveElement::egui_controls) of the elements.
     pub fn prepare_draw(
         &mut self,
         window: &mut FBox<RenderWindow>,
         egui_window: &mut SfEgui,
         counters: &Counter,
+        no_cursor: bool,
+        controls: &mut dyn FnMut(&mut egui::Ui),
     ) -> DrawInput {
-        self.overlay.set_string(&self.get_text(counters));
+        self.update_overlay(counters);
+        let show_controls = matches!(self.kind, InfoKind::Egui);
         egui_window
             .run(window, |_rw, ctx| {
-                let win = egui::Window::new("Info").fixed_size((300.0, 12.0));
+                // egui resets the cursor icon every pass, so it has to be set in here each frame
+                if no_cursor {
+                    ctx.set_cursor_icon(egui::CursorIcon::None);
+                }
+                let win = egui::Window::new(self.egui_title.as_str()).fixed_size(self.egui_size);
                 win.show(ctx, |ui| {
                     ui.label(self.get_text(counters));
+                    if show_controls {
+                        ui.separator();
+                        controls(ui);
+                    }
                 });
             })
             .unwrap()
@@ -126,40 +414,220 @@ impl<'s> Info<'s> {
         self.kind.next()
     }
 
+    /// Sets the title of the egui window, [Self::DEFAULT_NAME] by default
+    pub fn set_egui_title(&mut self, title: impl Into<String>) {
+        self.egui_title = title.into();
+    }
+
+    pub fn egui_title(&self) -> &str {
+        &self.egui_title
+    }
+
+    /// Sets the size of the egui window, [Self::DEFAULT_EGUI_SIZE] by default
+    pub fn set_egui_size(&mut self, size: egui::Vec2) {
+        self.egui_size = size;
+    }
+
+    pub fn egui_size(&self) -> egui::Vec2 {
+        self.egui_size
+    }
+
     pub fn set_kind(&mut self, kind: InfoKind) {
         self.kind = kind;
     }
 
     fn get_text(&self, counters: &Counter) -> String {
         let mut buf: String = format!("{}\n", counters.text);
-        for (key, value) in &self.custom_info {
-            if let Err(e) = writeln!(buf, "{key}: {value}") {
-                error!("could not write to format buffer for info widget: {e}");
-            }
+        if let Err(e) = self.write_custom_info(&mut buf) {
+            error!("could not write to format buffer for info widget: {e}");
         }
         buf
     }
 
+    fn write_custom_info(&self, buf: &mut String) -> std::fmt::Result {
+        for section in &self.custom_info {
+            if section.entries.is_empty() {
+                continue;
+            }
+            if let Some(name) = &section.name {
+                writeln!(buf, "[{name}]")?;
+            }
+            for (key, value) in &section.entries {
+                writeln!(buf, "{key}: {value}")?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn draw_with(
         &mut self,
         window: &mut FBox<RenderWindow>,
         egui_window: &mut SfEgui,
         counters: &Counter,
+        no_cursor: bool,
+        controls: &mut dyn FnMut(&mut egui::Ui),
     ) {
         match self.kind {
             InfoKind::None => (),
             InfoKind::Egui => {
-                let di = self.prepare_draw(window, egui_window, counters);
+                let di = self.prepare_draw(window, egui_window, counters, no_cursor, controls);
                 egui_window.draw(di, window, None);
             }
             InfoKind::Overlay => {
-                let _ = self.prepare_draw(window, egui_window, counters);
-                window.draw(&self.overlay)
+                let _ = self.prepare_draw(window, egui_window, counters, no_cursor, controls);
+                self.draw_overlay(&mut **window);
+            }
+            InfoKind::Graph => {
+                let _ = self.prepare_draw(window, egui_window, counters, no_cursor, controls);
+                self.draw_overlay(&mut **window);
+                self.update_graph(counters);
+                self.draw_graph(&mut **window);
+            }
+        }
+        self.draw_logo(&mut **window);
+    }
+
+    /// Like [Self::draw_with], but without egui, so it can draw to any target
+    ///
+    /// [InfoKind::Egui] is drawn like [InfoKind::Overlay] here.
+    pub fn draw_to(&mut self, target: &mut dyn RenderTarget, counters: &Counter) {
+        match self.kind {
+            InfoKind::None => (),
+            InfoKind::Egui | InfoKind::Overlay => {
+                self.update_overlay(counters);
+                self.draw_overlay(target);
+            }
+            InfoKind::Graph => {
+                self.update_overlay(counters);
+                self.draw_overlay(target);
+                self.update_graph(counters);
+                self.draw_graph(target);
             }
         }
+        self.draw_logo(target);
+    }
+
+    fn update_overlay(&mut self, counters: &Counter) {
+        let text = self.get_text(counters);
+        let lines: Vec<&str> = text.lines().collect();
+        let mut columns = lines.chunks(self.max_rows).map(|rows| rows.join("\n"));
+
+        self.overlay.set_string(&columns.next().unwrap_or_default());
+        let rest: Vec<String> = columns.collect();
+        // new columns look like the first one
+        self.columns
+            .resize_with(rest.len(), || self.overlay.clone());
+        for (column, text) in self.columns.iter_mut().zip(rest) {
+            column.set_string(&text);
+        }
+        self.update_overlay_position();
+    }
+
+    fn draw_overlay(&self, target: &mut dyn RenderTarget) {
+        target.draw(&self.overlay);
+        for column in &self.columns {
+            target.draw(column);
+        }
+    }
+
+    fn draw_graph(&self, target: &mut dyn RenderTarget) {
+        if let Some(graph_buf) = &self.graph_buf {
+            target.draw(&**graph_buf);
+        }
+    }
+
+    fn draw_logo(&self, target: &mut dyn RenderTarget) {
         if self.logo.is_some() && self.logo_text.is_some() {
-            window.draw(self.logo.as_ref().unwrap());
-            window.draw(self.logo_text.as_ref().unwrap());
+            target.draw(self.logo.as_ref().unwrap());
+            target.draw(self.logo_text.as_ref().unwrap());
+        }
+    }
+
+    /// Builds a bar for each of the recent frame times, next to the overlay on the side facing the
+    /// middle of the screen. Frames that took longer than [Counter::ms_per_frame] are red.
+    ///
+    /// The vertex buffer is only uploaded again when there is a new frame.
+    fn update_graph(&mut self, counters: &Counter) {
+        if self.graph_frame == Some(counters.frames) {
+            return;
+        }
+        self.graph_frame = Some(counters.frames);
+        self.graph.clear();
+
+        let bounds = self.overlay.global_bounds();
+        let left = if self.anchor.is_right() {
+            bounds.left + bounds.width - Self::GRAPH_WIDTH
+        } else {
+            bounds.left
+        };
+        let bottom = if self.anchor.is_bottom() {
+            bounds.top - Self::GRAPH_MARGIN
+        } else {
+            bounds.top + bounds.height + Self::GRAPH_MARGIN + Self::GRAPH_HEIGHT
+        };
+        let budget = counters.ms_per_frame();
+        // the budget is always in the middle of the graph, unless a frame took even longer
+        let max = counters
+            .frame_times
+            .iter()
+            .fold(budget * 2.0, |max, time| max.max(*time));
+        let step = Self::GRAPH_WIDTH / counters.frame_times.capacity().max(1) as f32;
+
+        for (i, time) in counters.frame_times.iter().enumerate() {
+            let x = left + i as f32 * step;
+            let height = time / max * Self::GRAPH_HEIGHT;
+            let color = if *time > budget {
+                Color::RED
+            } else {
+                Color::rgb(200, 200, 200)
+            };
+            self.graph
+                .push(Vertex::with_pos_color(Vector2f::new(x, bottom), color));
+            self.graph.push(Vertex::with_pos_color(
+                Vector2f::new(x, bottom - height),
+                color,
+            ));
+        }
+
+        let budget_y = bottom - budget / max * Self::GRAPH_HEIGHT;
+        let budget_color = Color::rgb(100, 100, 100);
+        self.graph.push(Vertex::with_pos_color(
+            Vector2f::new(left, budget_y),
+            budget_color,
+        ));
+        self.graph.push(Vertex::with_pos_color(
+            Vector2f::new(left + Self::GRAPH_WIDTH, budget_y),
+            budget_color,
+        ));
+
+        // the buffer has room for a full ring buffer, frames that were not measured yet are
+        // transparent
+        let vertex_count = counters.frame_times.capacity() * 2 + 2;
+        self.graph.resize(
+            vertex_count,
+            Vertex::with_pos_color(Vector2f::new(left, bottom), Color::TRANSPARENT),
+        );
+        if self
+            .graph_buf
+            .as_ref()
+            .is_none_or(|buf| buf.vertex_count() != vertex_count)
+        {
+            match VertexBuffer::new(
+                PrimitiveType::LINES,
+                vertex_count,
+                VertexBufferUsage::STREAM,
+            ) {
+                Ok(buf) => self.graph_buf = Some(buf),
+                Err(e) => {
+                    error!("could not create the vertex buffer of the graph: {e}");
+                    return;
+                }
+            }
+        }
+        if let Some(graph_buf) = &mut self.graph_buf
+            && let Err(e) = graph_buf.update(&self.graph, 0)
+        {
+            error!("could not update the vertices of the graph: {e}");
         }
     }
 
@@ -175,8 +643,4 @@ impl<'s> Info<'s> {
     pub fn z_level(&self) -> u16 {
         super::super::UI_Z_LEVEL
     }
-
-    pub fn video(&self) -> &VideoMode {
-        self.video
-    }
 }
@@ -0,0 +1,151 @@
+use std::f32::consts::TAU;
+
+use sfml::cpp::FBox;
+use sfml::graphics::{Color, PrimitiveType, RenderTarget, Vertex, VertexBuffer, VertexBufferUsage};
+use sfml::system::{Vector2f, Vector2u};
+use tracing::error;
+
+use crate::counter::Counter;
+use crate::errors::BwgResult;
+use crate::graphic::{ComprehensiveElement, UI_Z_LEVEL};
+
+use super::info::Info;
+
+/// line segments that make up the circle of [ReticleShape::Circle]
+const CIRCLE_SEGMENTS: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReticleShape {
+    #[default]
+    Cross,
+    Circle,
+}
+
+impl ReticleShape {
+    fn vertex_count(self) -> usize {
+        match self {
+            Self::Cross => 4,
+            Self::Circle => CIRCLE_SEGMENTS * 2,
+        }
+    }
+}
+
+/// A small mark at the center of the screen, to have something to orient by
+///
+/// The reticle follows the size of the [Info], so it stays centered when the window is resized.
+pub struct Reticle {
+    shape: ReticleShape,
+    /// half the width of the cross, or the radius of the circle, in pixels
+    size: f32,
+    color: Color,
+    vertices: FBox<VertexBuffer>,
+    /// screen size the vertices were made for
+    screen: Vector2u,
+}
+
+impl Reticle {
+    pub const DEFAULT_SIZE: f32 = 10.0;
+    pub const DEFAULT_COLOR: Color = Color::rgba(255, 255, 255, 160);
+
+    pub fn new(shape: ReticleShape) -> BwgResult<Self> {
+        Ok(Self {
+            shape,
+            size: Self::DEFAULT_SIZE,
+            color: Self::DEFAULT_COLOR,
+            vertices: VertexBuffer::new(
+                PrimitiveType::LINES,
+                shape.vertex_count(),
+                VertexBufferUsage::STATIC,
+            )?,
+            // nothing is drawn until the first update knows the screen size
+            screen: Vector2u::new(0, 0),
+        })
+    }
+
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.set_size(size);
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.set_color(color);
+        self
+    }
+
+    pub fn set_size(&mut self, size: f32) {
+        self.size = size;
+        self.update_vertices();
+    }
+
+    pub fn size(&self) -> f32 {
+        self.size
+    }
+
+    pub fn set_color(&mut self, color: Color) {
+        self.color = color;
+        self.update_vertices();
+    }
+
+    pub fn color(&self) -> Color {
+        self.color
+    }
+
+    fn update_vertices(&mut self) {
+        if self.screen.x == 0 || self.screen.y == 0 {
+            return;
+        }
+        let center = Vector2f::new(self.screen.x as f32 / 2.0, self.screen.y as f32 / 2.0);
+        let vertex = |offset: Vector2f| Vertex::with_pos_color(center + offset, self.color);
+        let vertices: Vec<Vertex> = match self.shape {
+            ReticleShape::Cross => vec![
+                vertex(Vector2f::new(-self.size, 0.0)),
+                vertex(Vector2f::new(self.size, 0.0)),
+                vertex(Vector2f::new(0.0, -self.size)),
+                vertex(Vector2f::new(0.0, self.size)),
+            ],
+            ReticleShape::Circle => (0..CIRCLE_SEGMENTS)
+                .flat_map(|i| [i, i + 1])
+                .map(|i| {
+                    let angle = i as f32 / CIRCLE_SEGMENTS as f32 * TAU;
+                    vertex(Vector2f::new(angle.cos(), angle.sin()) * self.size)
+                })
+                .collect(),
+        };
+        if let Err(e) = self.vertices.update(&vertices, 0) {
+            error!("could not update the vertices of the reticle: {e}");
+        }
+    }
+}
+
+impl<'s> ComprehensiveElement<'s> for Reticle {
+    fn z_level(&self) -> u16 {
+        UI_Z_LEVEL
+    }
+
+    fn update(&mut self, _counters: &Counter, info: &mut Info<'s>) {
+        if info.size() != self.screen {
+            self.screen = info.size();
+            self.update_vertices();
+        }
+    }
+
+    fn draw_to(
+        &mut self,
+        target: &mut dyn RenderTarget,
+        _counters: &Counter,
+        _info: &mut Info<'s>,
+    ) {
+        if self.screen.x == 0 || self.screen.y == 0 {
+            return;
+        }
+        target.draw(&*self.vertices);
+    }
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn std::any::Any> {
+        Some(self)
+    }
+}
@@ -0,0 +1,139 @@
+use sfml::graphics::{
+    Color, FloatRect, PrimitiveType, RenderTarget, Vertex, VertexBuffer, VertexBufferUsage,
+};
+use sfml::system::Vector2f;
+use tracing::error;
+
+/// A regular grid, to snap positions to its corners, for example when placing shapes
+///
+/// The grid starts at `(0, 0)` and goes on in all directions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Grid {
+    pub cell_size: Vector2f,
+    /// color of the grid lines in [Self::draw]
+    pub color: Color,
+}
+
+impl Grid {
+    pub const DEFAULT_COLOR: Color = Color::rgba(255, 255, 255, 40);
+
+    pub fn new(cell_size: impl Into<Vector2f>) -> Self {
+        Self {
+            cell_size: cell_size.into(),
+            color: Self::DEFAULT_COLOR,
+        }
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// The grid corner nearest to `p`
+    ///
+    /// A cell size of `0` or less on an axis leaves that axis as it is.
+    pub fn snap(&self, p: Vector2f) -> Vector2f {
+        let snap_axis = |value: f32, cell: f32| {
+            if cell <= 0.0 {
+                value
+            } else {
+                (value / cell).round() * cell
+            }
+        };
+        Vector2f::new(
+            snap_axis(p.x, self.cell_size.x),
+            snap_axis(p.y, self.cell_size.y),
+        )
+    }
+
+    /// Draws the grid lines that are inside of `bounds`
+    pub fn draw(&self, target: &mut dyn RenderTarget, bounds: FloatRect) {
+        if self.cell_size.x <= 0.0 || self.cell_size.y <= 0.0 {
+            return;
+        }
+        let right = bounds.left + bounds.width;
+        let bottom = bounds.top + bounds.height;
+        let line = |from: Vector2f, to: Vector2f| {
+            [
+                Vertex::with_pos_color(from, self.color),
+                Vertex::with_pos_color(to, self.color),
+            ]
+        };
+
+        let mut vertices = Vec::new();
+        let mut x = (bounds.left / self.cell_size.x).ceil() * self.cell_size.x;
+        while x <= right {
+            vertices.extend(line(Vector2f::new(x, bounds.top), Vector2f::new(x, bottom)));
+            x += self.cell_size.x;
+        }
+        let mut y = (bounds.top / self.cell_size.y).ceil() * self.cell_size.y;
+        while y <= bottom {
+            vertices.extend(line(Vector2f::new(bounds.left, y), Vector2f::new(right, y)));
+            y += self.cell_size.y;
+        }
+        if vertices.is_empty() {
+            return;
+        }
+
+        let buffer = VertexBuffer::new(
+            PrimitiveType::LINES,
+            vertices.len(),
+            VertexBufferUsage::STREAM,
+        )
+        .and_then(|mut buffer| {
+            buffer.update(&vertices, 0)?;
+            Ok(buffer)
+        });
+        match buffer {
+            Ok(buffer) => target.draw(&*buffer),
+            Err(e) => error!("could not create the vertex buffer for the grid: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snap(grid: &Grid, x: f32, y: f32) -> (f32, f32) {
+        let p = grid.snap(Vector2f::new(x, y));
+        (p.x, p.y)
+    }
+
+    #[test]
+    fn snaps_positive_values_to_the_nearest_corner() {
+        let grid = Grid::new((10.0, 25.0));
+        assert_eq!(snap(&grid, 3.0, 12.0), (0.0, 0.0));
+        assert_eq!(snap(&grid, 7.0, 13.0), (10.0, 25.0));
+        assert_eq!(snap(&grid, 123.4, 80.0), (120.0, 75.0));
+    }
+
+    #[test]
+    fn snaps_negative_values_to_the_nearest_corner() {
+        let grid = Grid::new((10.0, 25.0));
+        assert_eq!(snap(&grid, -3.0, -12.0), (0.0, 0.0));
+        assert_eq!(snap(&grid, -7.0, -13.0), (-10.0, -25.0));
+        assert_eq!(snap(&grid, -123.4, -80.0), (-120.0, -75.0));
+    }
+
+    #[test]
+    fn values_on_a_corner_stay_there() {
+        let grid = Grid::new((10.0, 25.0));
+        for (x, y) in [(0.0, 0.0), (10.0, 25.0), (-10.0, -25.0), (-250.0, 500.0)] {
+            assert_eq!(snap(&grid, x, y), (x, y));
+        }
+    }
+
+    #[test]
+    fn halfway_values_round_away_from_zero() {
+        let grid = Grid::new((10.0, 10.0));
+        assert_eq!(snap(&grid, 5.0, 15.0), (10.0, 20.0));
+        assert_eq!(snap(&grid, -5.0, -15.0), (-10.0, -20.0));
+    }
+
+    #[test]
+    fn axes_without_a_cell_size_are_not_snapped() {
+        let grid = Grid::new((0.0, -5.0));
+        assert_eq!(snap(&grid, 3.3, -7.7), (3.3, -7.7));
+    }
+}
@@ -1,26 +1,64 @@
+use std::any::Any;
 use std::collections::HashMap;
+use std::ops::DerefMut;
 
 use egui_sfml::SfEgui;
 use sfml::cpp::FBox;
-use sfml::graphics::{Font, RenderWindow};
-use sfml::window::{Event, VideoMode};
+use sfml::graphics::{Drawable, FloatRect, Font, RenderTarget, RenderTexture, RenderWindow};
+use sfml::system::{Vector2i, Vector2u};
+use sfml::window::{Event, Key, VideoMode};
+use tracing::{debug, error};
 
 use crate::counter::Counter;
-use crate::errors::BwgResult;
+use crate::errors::{BwgError, BwgResult};
+use crate::id::Id;
 
+use self::bus::EventBus;
+use self::camera::Camera;
 use self::elements::info::Info;
+use self::nativeui::elements::NativeElement;
 
+/// Elements and drawables from this z-level on are part of the HUD, they are drawn in screen
+/// coordinates and do not move with the [Camera]
 pub const UI_Z_LEVEL: u16 = 20000;
 pub const DEFAULT_Z_LEVEL: u16 = 1000;
 
+pub mod bus;
+pub mod camera;
 pub mod elements;
+pub mod grid;
 pub mod nativeui;
+pub mod projection;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod window;
 
 pub trait ComprehensiveElement<'s>: 's {
     fn z_level(&self) -> u16 {
         DEFAULT_Z_LEVEL
     }
 
+    /// Elements that return `false` here are not drawn, but still receive updates and events.
+    ///
+    /// Implementors that want to be hidden and shown at runtime should keep their own flag and
+    /// return it here.
+    fn visible(&self) -> bool {
+        true
+    }
+
+    /// The area the element draws to, in world coordinates
+    ///
+    /// Elements whose bounds are outside of the view of the camera are not drawn. `None` means
+    /// the element is always drawn, which is right for elements that cover the whole screen.
+    /// Elements from [UI_Z_LEVEL] on are drawn in screen coordinates and never culled.
+    fn bounds(&self) -> Option<FloatRect> {
+        None
+    }
+
+    /// Draws the element to the window
+    ///
+    /// By default, this uses [Self::draw_to]. Only elements that need egui have to implement this
+    /// themselves.
     #[allow(unused_variables)]
     fn draw_with(
         &mut self,
@@ -29,57 +67,234 @@ pub trait ComprehensiveElement<'s>: 's {
         counters: &Counter,
         info: &mut Info<'s>,
     ) {
+        self.draw_to(&mut **sfml_w, counters, info);
     }
 
+    /// Draws the element to any target, like a [RenderTexture]
+    ///
+    /// egui can only draw to a [RenderWindow], so anything drawn with egui is missing when
+    /// drawing to other targets.
+    #[allow(unused_variables)]
+    fn draw_to(&mut self, target: &mut dyn RenderTarget, counters: &Counter, info: &mut Info<'s>) {}
+
+    /// Lets [ComprehensiveUi::get_as] get the concrete type of this element back
+    ///
+    /// [Any] only works for `'static` types, so this can not be implemented for all elements.
+    /// Elements that are `'static` can return `Some(self)`.
+    fn as_any(&self) -> Option<&dyn Any> {
+        None
+    }
+    /// Mutable version of [Self::as_any], for [ComprehensiveUi::get_as_mut]
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+        None
+    }
+
+    /// Adds widgets to the egui [Info] window, to tune the element while it runs
+    ///
+    /// This is only called while the [Info] is shown with egui. Changes should take effect on the
+    /// next update. Messages posted to `bus`, for example when a button is clicked, are
+    /// delivered to [Self::handle_messages] on the next update.
+    #[allow(unused_variables)]
+    fn egui_controls(&mut self, ui: &mut egui::Ui, bus: &mut EventBus) {}
+
+    /// Receives the messages that were posted since the last update, by the
+    /// [app](ComprehensiveUi::post) or by [elements](Info::post)
+    ///
+    /// This is called before [Self::update] of every element, but only if there are messages.
+    /// Messages posted while handling these are delivered on the next update.
+    #[allow(unused_variables)]
+    fn handle_messages(&mut self, bus: &EventBus, counters: &Counter, info: &mut Info<'s>) {}
+
     #[allow(unused_variables)]
     fn process_event(&mut self, event: &Event, counters: &Counter, info: &mut Info<'s>) {}
     #[allow(unused_variables)]
     fn update_slow(&mut self, counters: &Counter, info: &mut Info<'s>) {}
     #[allow(unused_variables)]
     fn update(&mut self, counters: &Counter, info: &mut Info<'s>) {}
+    /// Like [Self::update], but errors are returned and logged by [ComprehensiveUi::update]
+    ///
+    /// By default, this calls [Self::update]. Elements whose update can fail should implement
+    /// this instead of [Self::update].
+    fn try_update(&mut self, counters: &Counter, info: &mut Info<'s>) -> BwgResult<()> {
+        self.update(counters, info);
+        Ok(())
+    }
 }
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Hash, Default)]
-pub struct GElementID {
-    inner: u128,
+pub struct GraphicMarker;
+
+pub type GElementID = Id<GraphicMarker>;
+
+pub struct ComprehensiveUi<'s> {
+    egui_window: SfEgui,
+    pub font: &'s FBox<Font>,
+    pub info: Info<'s>,
+    elements: Elements<'s>,
+    /// names of the elements that were added with [Self::add_named]
+    names: HashMap<GElementID, String>,
+    /// drawn in screen coordinates, above the elements
+    native_elements: HashMap<GElementID, Box<dyn NativeElement<'s>>>,
+    pub counter: Counter,
+    no_cursor: bool,
+    /// the frame rate follows the display instead of the limit of the window
+    vsync: bool,
+    /// measure how long each element takes to update and draw, and show it in the [Info]
+    profiling: bool,
+    camera: Camera,
+    /// last known mouse position, for events that do not have one
+    mouse_pos: Vector2i,
+    /// elements are only updated when a step was requested, see [Self::set_step_mode]
+    step_mode: bool,
+    /// update the elements once in step mode
+    request_step: bool,
+    /// keys that toggle step mode and request a step, see [Self::set_step_keys]
+    step_keys: Option<(Key, Key)>,
+    /// messages for the elements, delivered and cleared in [Self::update]
+    bus: EventBus,
+    /// drawables with their z-level, see [Self::add_drawable]
+    drawables: Drawables<'s>,
+}
+
+type Elements<'s> = HashMap<GElementID, Box<dyn ComprehensiveElement<'s>>>;
+/// Sorted by the z-level, and in the order they were added within a z-level
+type Drawables<'s> = Vec<(GElementID, u16, Box<dyn Drawable + 's>)>;
+
+/// Whether an element is [visible](ComprehensiveElement::visible) and its
+/// [bounds](ComprehensiveElement::bounds) are in the view
+///
+/// Elements from [UI_Z_LEVEL] on are drawn in screen coordinates, so they are never culled.
+fn should_draw<'s>(element: &(dyn ComprehensiveElement<'s> + 's), view_rect: &FloatRect) -> bool {
+    element.visible()
+        && (element.z_level() >= UI_Z_LEVEL
+            || element
+                .bounds()
+                .is_none_or(|bounds| bounds.intersection(view_rect).is_some()))
 }
 
-impl rand::distr::Distribution<GElementID> for rand::distr::StandardUniform {
-    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> GElementID {
-        GElementID {
-            inner: rng.random(),
+/// Draws the elements and the drawables in the order of their z-level
+///
+/// Everything below [UI_Z_LEVEL] is drawn through the camera, everything from there on in screen
+/// coordinates. The target keeps the screen view afterwards.
+fn draw_layers<'s, W>(
+    target: &mut W,
+    camera: &Camera,
+    elements: &mut Elements<'s>,
+    drawables: &Drawables<'s>,
+    mut draw_element: impl FnMut(&mut W, &GElementID, &mut (dyn ComprehensiveElement<'s> + 's)),
+) where
+    W: DerefMut,
+    W::Target: RenderTarget,
+{
+    camera.apply(&mut **target);
+    let view_rect = camera.visible_rect();
+    let mut screen = false;
+    let mut use_view = |target: &mut W, z_level: u16| {
+        if z_level >= UI_Z_LEVEL && !screen {
+            camera.apply_screen(&mut **target);
+            screen = true;
+        }
+    };
+    let mut drawables = drawables.iter().peekable();
+    for (id, element) in sorted_elements(elements) {
+        let z_level = element.z_level();
+        while let Some((_, z, drawable)) = drawables.next_if(|(_, z, _)| *z < z_level) {
+            use_view(target, *z);
+            target.draw(&**drawable);
         }
+        use_view(target, z_level);
+        if should_draw(&**element, &view_rect) {
+            draw_element(target, id, &mut **element);
+        }
+    }
+    for (_, z, drawable) in drawables {
+        use_view(target, *z);
+        target.draw(&**drawable);
     }
+    use_view(target, UI_Z_LEVEL);
 }
 
-impl GElementID {
-    pub fn new() -> Self {
-        rand::random()
+/// Key of the [Info] entry for the time an element took for `what`
+fn profiling_key<'s>(
+    id: &GElementID,
+    name: Option<&String>,
+    element: &(dyn ComprehensiveElement<'s> + 's),
+    what: &str,
+) -> String {
+    match name {
+        Some(name) => format!("elem_{name}_{what}_ms"),
+        // the start of the id is enough to tell elements apart
+        None => format!(
+            "elem_z{}_{:04x}_{what}_ms",
+            element.z_level(),
+            id.as_u128() >> 112
+        ),
     }
 }
 
-pub struct ComprehensiveUi<'s> {
-    egui_window: SfEgui,
-    pub font: &'s FBox<Font>,
-    pub info: Info<'s>,
-    elements: HashMap<GElementID, Box<dyn ComprehensiveElement<'s>>>,
-    pub counter: Counter,
+/// Logs the error of a failed [ComprehensiveElement::try_update]
+fn log_update_error(id: &GElementID, name: Option<&String>, result: BwgResult<()>) {
+    if let Err(e) = result {
+        match name {
+            Some(name) => error!("could not update the element {name}: {e}"),
+            None => error!("could not update the element {id:?}: {e}"),
+        }
+    }
+}
+
+/// The hashmap has no order, so elements with a lower z_level need to be sorted to the front.
+/// Elements with the same z_level are sorted by their id, so that the order stays the same.
+fn sorted_elements<'a, 's>(
+    elements: &'a mut Elements<'s>,
+) -> Vec<(
+    &'a GElementID,
+    &'a mut Box<dyn ComprehensiveElement<'s> + 's>,
+)> {
+    let mut elements: Vec<_> = elements.iter_mut().collect();
+    elements.sort_by_key(|(id, element)| (element.z_level(), **id));
+    elements
 }
 
 impl<'s> ComprehensiveUi<'s> {
+    /// The keys to toggle the step mode and to request a step, see [Self::set_step_keys]
+    pub const DEFAULT_STEP_KEYS: (Key, Key) = (Key::M, Key::N);
+
     pub fn add_event(&mut self, event: &Event) {
         self.egui_window.add_event(event);
 
+        match *event {
+            Event::MouseMoved { x, y }
+            | Event::MouseButtonPressed { x, y, .. }
+            | Event::MouseButtonReleased { x, y, .. }
+            | Event::MouseWheelScrolled { x, y, .. } => self.mouse_pos = Vector2i::new(x, y),
+            Event::Resized { width, height } => self.resize(Vector2u::new(width, height)),
+            _ => (),
+        }
+        // keys that are typed into a focused native element are not meant for the ui
+        if let Event::KeyPressed { code, .. } = *event
+            && let Some((toggle, step)) = self.step_keys
+            && !self.native_focused()
+        {
+            if code == toggle {
+                self.set_step_mode(!self.step_mode);
+            } else if code == step {
+                self.request_step();
+            }
+        }
+
         for element in self.elements.values_mut() {
             element.process_event(event, &self.counter, &mut self.info);
         }
+        for element in self.native_elements.values_mut() {
+            element.handle_event(event, self.mouse_pos);
+        }
         self.info.process_event(event);
     }
 
     pub fn build(
         window: &mut FBox<RenderWindow>,
         font: &'s FBox<Font>,
-        video: &'s VideoMode,
+        video: &VideoMode,
         fps_limit: u64,
     ) -> BwgResult<Self> {
         let counters = Counter::start(fps_limit)?;
@@ -88,9 +303,21 @@ impl<'s> ComprehensiveUi<'s> {
         let gui = Self {
             egui_window: SfEgui::new(window),
             elements: HashMap::new(),
+            names: HashMap::new(),
+            native_elements: HashMap::new(),
             info: Info::new(font, video, &counters),
             font,
             counter: counters,
+            no_cursor: false,
+            vsync: false,
+            profiling: false,
+            camera: Camera::new((video.width as f32, video.height as f32)),
+            mouse_pos: Vector2i::new(0, 0),
+            step_mode: false,
+            request_step: false,
+            step_keys: None,
+            bus: EventBus::new(),
+            drawables: Vec::new(),
         };
         Ok(gui)
     }
@@ -101,6 +328,24 @@ impl<'s> ComprehensiveUi<'s> {
         id
     }
 
+    /// Like [Self::add], but with a name that is used in logs and the profiling HUD
+    pub fn add_named(
+        &mut self,
+        name: impl Into<String>,
+        element: Box<dyn ComprehensiveElement<'s>>,
+    ) -> GElementID {
+        let id = self.add(element);
+        let name = name.into();
+        debug!("added element '{name}' with id {id:?}");
+        self.names.insert(id, name);
+        id
+    }
+
+    /// The name an element was added with, see [Self::add_named]
+    pub fn name_of(&self, id: &GElementID) -> Option<&str> {
+        self.names.get(id).map(String::as_str)
+    }
+
     pub fn get(&self, id: &GElementID) -> Option<&dyn ComprehensiveElement<'s>> {
         self.elements.get(id).map(|v| &**v)
     }
@@ -109,12 +354,184 @@ impl<'s> ComprehensiveUi<'s> {
         self.elements.get_mut(id).map(|v| &mut **v)
     }
 
+    pub fn remove(&mut self, id: &GElementID) -> Option<Box<dyn ComprehensiveElement<'s>>> {
+        if let Some(name) = self.names.remove(id) {
+            debug!("removed element '{name}'");
+        }
+        self.elements.remove(id)
+    }
+
+    /// Gets an element as its concrete type
+    ///
+    /// Returns `None` if there is no such element, if it has another type, or if it does not
+    /// implement [ComprehensiveElement::as_any].
+    pub fn get_as<T: ComprehensiveElement<'s> + Any>(&self, id: &GElementID) -> Option<&T> {
+        self.get(id)?.as_any()?.downcast_ref()
+    }
+
+    /// Mutable version of [Self::get_as], the element needs to implement
+    /// [ComprehensiveElement::as_any_mut]
+    pub fn get_as_mut<T: ComprehensiveElement<'s> + Any>(
+        &mut self,
+        id: &GElementID,
+    ) -> Option<&mut T> {
+        self.get_mut(id)?.as_any_mut()?.downcast_mut()
+    }
+
+    /// Like [Self::get], but with an error if there is no such element
+    pub fn try_get(&self, id: &GElementID) -> BwgResult<&dyn ComprehensiveElement<'s>> {
+        self.get(id).ok_or(BwgError::ElementNotFound(id.as_u128()))
+    }
+
+    /// Like [Self::get_mut], but with an error if there is no such element
+    pub fn try_get_mut(&mut self, id: &GElementID) -> BwgResult<&mut dyn ComprehensiveElement<'s>> {
+        self.get_mut(id)
+            .ok_or(BwgError::ElementNotFound(id.as_u128()))
+    }
+
+    /// Like [Self::remove], but with an error if there is no such element
+    pub fn try_remove(&mut self, id: &GElementID) -> BwgResult<Box<dyn ComprehensiveElement<'s>>> {
+        self.remove(id)
+            .ok_or(BwgError::ElementNotFound(id.as_u128()))
+    }
+
+    /// The ids of all elements, in the order they are drawn
+    pub fn ids(&self) -> impl Iterator<Item = GElementID> + '_ {
+        let mut ids: Vec<_> = self
+            .elements
+            .iter()
+            .map(|(id, element)| (element.z_level(), *id))
+            .collect();
+        ids.sort();
+        ids.into_iter().map(|(_z_level, id)| id)
+    }
+
+    /// All elements, in the order they are drawn
+    pub fn iter_mut(
+        &mut self,
+    ) -> impl Iterator<Item = (&GElementID, &mut dyn ComprehensiveElement<'s>)> {
+        sorted_elements(&mut self.elements)
+            .into_iter()
+            .map(|(id, element)| (id, &mut **element))
+    }
+
+    /// Adds a native UI element, which gets events and is drawn over all other elements
+    pub fn add_native(&mut self, element: Box<dyn NativeElement<'s>>) -> GElementID {
+        let id = self.get_new_element_id();
+        self.native_elements.insert(id, element);
+        id
+    }
+
+    pub fn get_native(&self, id: &GElementID) -> Option<&dyn NativeElement<'s>> {
+        self.native_elements.get(id).map(|v| &**v)
+    }
+
+    pub fn get_native_mut(&mut self, id: &GElementID) -> Option<&mut dyn NativeElement<'s>> {
+        self.native_elements.get_mut(id).map(|v| &mut **v)
+    }
+
+    pub fn remove_native(&mut self, id: &GElementID) -> Option<Box<dyn NativeElement<'s>>> {
+        self.native_elements.remove(id)
+    }
+
+    /// Removes all elements, native elements and drawables, the [Info] overlay stays intact
+    pub fn clear(&mut self) {
+        self.elements.clear();
+        self.names.clear();
+        self.native_elements.clear();
+        self.drawables.clear();
+    }
+
+    /// Draws `drawable` in every [Self::draw_with] and [Self::draw_to_texture], between the
+    /// elements by its z-level, until it is [removed](Self::remove_drawable)
+    ///
+    /// This is for simple shapes that do not need a whole [ComprehensiveElement]. It is drawn
+    /// after the elements and the drawables that were added before with the same z-level.
+    pub fn add_drawable(&mut self, drawable: Box<dyn Drawable + 's>, z_level: u16) -> GElementID {
+        let id = self.get_new_element_id();
+        let index = self.drawables.partition_point(|(_, z, _)| *z <= z_level);
+        self.drawables.insert(index, (id, z_level, drawable));
+        id
+    }
+
+    pub fn remove_drawable(&mut self, id: &GElementID) -> Option<Box<dyn Drawable + 's>> {
+        let index = self
+            .drawables
+            .iter()
+            .position(|(other, _, _)| other == id)?;
+        Some(self.drawables.remove(index).2)
+    }
+
     pub fn draw_with(&mut self, window: &mut FBox<RenderWindow>) {
-        for element in self.elements.values_mut() {
-            element.draw_with(window, &mut self.egui_window, &self.counter, &mut self.info);
+        let draw_element =
+            |window: &mut FBox<RenderWindow>,
+             id: &GElementID,
+             element: &mut (dyn ComprehensiveElement<'s> + 's)| {
+                if !self.profiling {
+                    element.draw_with(window, &mut self.egui_window, &self.counter, &mut self.info);
+                    return;
+                }
+                let start = self.counter.clock.elapsed_time().as_seconds();
+                element.draw_with(window, &mut self.egui_window, &self.counter, &mut self.info);
+                let ms = (self.counter.clock.elapsed_time().as_seconds() - start) * 1000.0;
+                self.info.set_custom_info(
+                    profiling_key(id, self.names.get(id), element, "draw"),
+                    format_args!("{ms:.3}"),
+                );
+            };
+        draw_layers(
+            window,
+            &self.camera,
+            &mut self.elements,
+            &self.drawables,
+            draw_element,
+        );
+        // the HUD should stay in place, no matter where the camera is, draw_layers left the
+        // screen view
+        for element in self.native_elements.values() {
+            window.draw(&**element);
+        }
+        let elements = &mut self.elements;
+        // the info is borrowed while the controls run, so their messages are collected here
+        let bus = &mut self.bus;
+        let mut controls = |ui: &mut egui::Ui| {
+            for (_id, element) in sorted_elements(elements) {
+                element.egui_controls(ui, bus);
+            }
+        };
+        self.info.draw_with(
+            window,
+            &mut self.egui_window,
+            &self.counter,
+            self.no_cursor,
+            &mut controls,
+        );
+        if self.no_cursor {
+            // egui makes the cursor visible again when it runs, so this needs to happen every frame
+            window.set_mouse_cursor_visible(false);
+        }
+    }
+
+    /// Like [Self::draw_with], but renders the scene into `target` instead of the window, for
+    /// recording or post processing
+    ///
+    /// egui can only draw to the window, so elements are drawn with
+    /// [ComprehensiveElement::draw_to] and an egui [Info] is drawn as an overlay. Call
+    /// [RenderTexture::display] once everything is drawn.
+    pub fn draw_to_texture(&mut self, target: &mut FBox<RenderTexture>) {
+        draw_layers(
+            target,
+            &self.camera,
+            &mut self.elements,
+            &self.drawables,
+            |target, _id, element| {
+                element.draw_to(&mut **target, &self.counter, &mut self.info);
+            },
+        );
+        for element in self.native_elements.values() {
+            target.draw(&**element);
         }
-        self.info
-            .draw_with(window, &mut self.egui_window, &self.counter);
+        self.info.draw_to(&mut **target, &self.counter);
     }
 
     pub fn update_slow(&mut self) {
@@ -125,20 +542,150 @@ impl<'s> ComprehensiveUi<'s> {
     }
 
     pub fn update(&mut self) {
-        for element in self.elements.values_mut() {
-            element.update(&self.counter, &mut self.info);
+        if self.step_mode && !self.request_step {
+            return;
+        }
+        self.request_step = false;
+        // swapped out, so that the messages posted while these are handled wait for the next
+        // update instead of being delivered right away
+        let mut bus = std::mem::take(&mut self.bus);
+        bus.append(self.info.outbox_mut());
+        if !bus.is_empty() {
+            for element in self.elements.values_mut() {
+                element.handle_messages(&bus, &self.counter, &mut self.info);
+            }
+        }
+        for (id, element) in self.elements.iter_mut() {
+            if !self.profiling {
+                let result = element.try_update(&self.counter, &mut self.info);
+                log_update_error(id, self.names.get(id), result);
+                continue;
+            }
+            let start = self.counter.clock.elapsed_time().as_seconds();
+            let result = element.try_update(&self.counter, &mut self.info);
+            log_update_error(id, self.names.get(id), result);
+            let ms = (self.counter.clock.elapsed_time().as_seconds() - start) * 1000.0;
+            self.info.set_custom_info(
+                profiling_key(id, self.names.get(id), &**element, "update"),
+                format_args!("{ms:.3}"),
+            );
         }
         self.info.update(&self.counter);
     }
 
-    // BUG: this does not work
-    pub fn set_no_cursor(&self, window: &mut FBox<RenderWindow>, arg: bool) {
-        window.set_mouse_cursor_visible(arg);
-        self.egui_window.context().set_cursor_icon(if arg {
-            egui::CursorIcon::None
+    /// Sends a message to all elements, see [ComprehensiveElement::handle_messages]
+    ///
+    /// Messages are delivered on the next [Self::update] and dropped afterwards. In step mode,
+    /// they wait for the next step.
+    pub fn post<T: Any>(&mut self, msg: T) {
+        self.bus.post(msg);
+    }
+
+    /// Adapts the camera and the [Info] overlay to a new window size
+    ///
+    /// This is done automatically for [Event::Resized] in [Self::add_event].
+    pub fn resize(&mut self, size: Vector2u) {
+        self.camera.resize((size.x as f32, size.y as f32));
+        self.info.set_size(size);
+    }
+
+    /// In step mode, the elements are only updated once for every [Self::request_step], but
+    /// still drawn every frame. This can also be toggled with the [step keys](Self::set_step_keys).
+    pub fn set_step_mode(&mut self, step_mode: bool) {
+        debug!("step mode: {step_mode}");
+        self.step_mode = step_mode;
+        self.request_step = false;
+    }
+
+    pub fn step_mode(&self) -> bool {
+        self.step_mode
+    }
+
+    /// Sets the keys that toggle the step mode and request a step, `None` disables them
+    ///
+    /// They are disabled by default, [Self::DEFAULT_STEP_KEYS] is a good choice to enable them.
+    /// The keys are ignored while a native element has the focus, so they can still be typed.
+    pub fn set_step_keys(&mut self, keys: Option<(Key, Key)>) {
+        self.step_keys = keys;
+    }
+
+    pub fn step_keys(&self) -> Option<(Key, Key)> {
+        self.step_keys
+    }
+
+    /// Whether one of the native elements has the keyboard focus
+    fn native_focused(&self) -> bool {
+        self.native_elements
+            .values()
+            .any(|element| element.is_focused())
+    }
+
+    /// Updates the elements once on the next [Self::update] while in step mode, also done with
+    /// the [step keys](Self::set_step_keys)
+    pub fn request_step(&mut self) {
+        if self.step_mode {
+            self.request_step = true;
+        }
+    }
+
+    pub fn set_no_cursor(&mut self, window: &mut FBox<RenderWindow>, no_cursor: bool) {
+        self.no_cursor = no_cursor;
+        window.set_mouse_cursor_visible(!no_cursor);
+    }
+
+    pub fn no_cursor(&self) -> bool {
+        self.no_cursor
+    }
+
+    /// Waits for the vertical sync of the display, which can be smoother and avoids tearing
+    ///
+    /// Vsync and the frame rate limit of the window do not work together, using both at once
+    /// gives undefined frame rates. So the limit of the window is turned off while vsync is
+    /// enabled, and set to the fps limit of the [Counter] again when it is disabled. The
+    /// [Counter] keeps its fps limit either way, for the slow updates.
+    pub fn set_vsync(&mut self, window: &mut FBox<RenderWindow>, enabled: bool) {
+        self.vsync = enabled;
+        window.set_vertical_sync_enabled(enabled);
+        if enabled {
+            window.set_framerate_limit(0);
         } else {
-            egui::CursorIcon::default()
-        });
+            window.set_framerate_limit(self.counter.fps_limit as u32);
+        }
+    }
+
+    pub fn vsync(&self) -> bool {
+        self.vsync
+    }
+
+    /// Shows how long each element takes to update and draw in the [Info]
+    ///
+    /// Nothing is measured while this is off.
+    pub fn set_profiling(&mut self, profiling: bool) {
+        self.profiling = profiling;
+        if !profiling {
+            for (id, element) in self.elements.iter() {
+                for what in ["update", "draw"] {
+                    self.info.remove_custom_info(profiling_key(
+                        id,
+                        self.names.get(id),
+                        &**element,
+                        what,
+                    ));
+                }
+            }
+        }
+    }
+
+    pub fn profiling(&self) -> bool {
+        self.profiling
+    }
+
+    pub fn camera(&self) -> &Camera {
+        &self.camera
+    }
+
+    pub fn camera_mut(&mut self) -> &mut Camera {
+        &mut self.camera
     }
 
     pub fn frame_start(&mut self) {
@@ -156,7 +703,10 @@ impl<'s> ComprehensiveUi<'s> {
         loop {
             id = rand::random();
 
-            if !self.elements.contains_key(&id) {
+            if !self.elements.contains_key(&id)
+                && !self.native_elements.contains_key(&id)
+                && !self.drawables.iter().any(|(other, _, _)| *other == id)
+            {
                 break;
             }
             if guard > 20 {
@@ -169,3 +719,249 @@ impl<'s> ComprehensiveUi<'s> {
         id
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use sfml::graphics::{
+        CircleShape, Color, ConvexShape, CustomShape, IntRect, PrimitiveType, RcSprite, RcText,
+        RectangleShape, RenderStates, Sprite, Text, Vertex, VertexBuffer, View,
+    };
+    use sfml::system::Vector2f;
+
+    use super::elements::reticle::{Reticle, ReticleShape};
+    use super::*;
+
+    /// A render target that only remembers the view of every draw call
+    ///
+    /// Nothing is rendered, but views and coordinates work like on a real target of
+    /// [Self::SIZE].
+    struct RecordingTarget {
+        view: FBox<View>,
+        default_view: FBox<View>,
+        /// center and size of the view at each draw
+        draws: Vec<(Vector2f, Vector2f)>,
+    }
+
+    impl RecordingTarget {
+        const SIZE: Vector2u = Vector2u::new(800, 600);
+
+        fn new() -> Self {
+            let size = Self::SIZE.as_other::<f32>();
+            let default_view = View::with_center_and_size(size / 2.0, size);
+            Self {
+                view: default_view.to_owned(),
+                default_view,
+                draws: Vec::new(),
+            }
+        }
+
+        fn record(&mut self) {
+            self.draws.push((self.view.center(), self.view.size()));
+        }
+    }
+
+    impl RenderTarget for RecordingTarget {
+        fn clear(&mut self, _color: Color) {}
+        fn view(&self) -> &View {
+            &self.view
+        }
+        fn default_view(&self) -> &View {
+            &self.default_view
+        }
+        fn set_view(&mut self, view: &View) {
+            self.view = view.to_owned();
+        }
+        fn viewport(&self, _view: &View) -> IntRect {
+            IntRect::new(0, 0, Self::SIZE.x as i32, Self::SIZE.y as i32)
+        }
+        fn map_pixel_to_coords(&self, point: Vector2i, view: &View) -> Vector2f {
+            let scale = view.size().cwise_div(Self::SIZE.as_other());
+            view.center() - view.size() / 2.0 + point.as_other::<f32>().cwise_mul(scale)
+        }
+        fn map_pixel_to_coords_current_view(&self, point: Vector2i) -> Vector2f {
+            self.map_pixel_to_coords(point, &self.view)
+        }
+        fn map_coords_to_pixel(&self, point: Vector2f, view: &View) -> Vector2i {
+            let scale = Self::SIZE.as_other::<f32>().cwise_div(view.size());
+            (point - view.center() + view.size() / 2.0)
+                .cwise_mul(scale)
+                .as_other()
+        }
+        fn map_coords_to_pixel_current_view(&self, point: Vector2f) -> Vector2i {
+            self.map_coords_to_pixel(point, &self.view)
+        }
+        fn draw(&mut self, _object: &dyn Drawable) {
+            self.record();
+        }
+        fn draw_with_renderstates(&mut self, _object: &dyn Drawable, _rs: &RenderStates) {
+            self.record();
+        }
+        fn size(&self) -> Vector2u {
+            Self::SIZE
+        }
+        fn push_gl_states(&mut self) {}
+        fn pop_gl_states(&mut self) {}
+        fn reset_gl_states(&mut self) {}
+        fn draw_text(&mut self, _text: &Text, _rs: &RenderStates) {
+            self.record();
+        }
+        fn draw_rc_text(&mut self, _text: &RcText, _rs: &RenderStates) {
+            self.record();
+        }
+        fn draw_shape(&mut self, _shape: &CustomShape, _rs: &RenderStates) {
+            self.record();
+        }
+        fn draw_sprite(&mut self, _sprite: &Sprite, _rs: &RenderStates) {
+            self.record();
+        }
+        fn draw_rc_sprite(&mut self, _sprite: &RcSprite, _rs: &RenderStates) {
+            self.record();
+        }
+        fn draw_circle_shape(&mut self, _circle_shape: &CircleShape, _rs: &RenderStates) {
+            self.record();
+        }
+        fn draw_rectangle_shape(&mut self, _rectangle_shape: &RectangleShape, _rs: &RenderStates) {
+            self.record();
+        }
+        fn draw_convex_shape(&mut self, _convex_shape: &ConvexShape, _rs: &RenderStates) {
+            self.record();
+        }
+        fn draw_vertex_buffer(&mut self, _vertex_buffer: &VertexBuffer, _rs: &RenderStates) {
+            self.record();
+        }
+        fn draw_primitives(
+            &mut self,
+            _vertices: &[Vertex],
+            _ty: PrimitiveType,
+            _rs: &RenderStates,
+        ) {
+            self.record();
+        }
+    }
+
+    /// Draws nothing, the [RecordingTarget] only counts it
+    struct Mark;
+
+    impl Drawable for Mark {
+        fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
+            &'a self,
+            _target: &mut dyn RenderTarget,
+            _states: &sfml::graphics::RenderStates<'texture, 'shader, 'shader_texture>,
+        ) {
+        }
+    }
+
+    struct Rect {
+        bounds: FloatRect,
+        z_level: u16,
+    }
+
+    impl ComprehensiveElement<'_> for Rect {
+        fn z_level(&self) -> u16 {
+            self.z_level
+        }
+
+        fn bounds(&self) -> Option<FloatRect> {
+            Some(self.bounds)
+        }
+    }
+
+    fn rect<'s>(left: f32, top: f32, z_level: u16) -> Box<dyn ComprehensiveElement<'s>> {
+        Box::new(Rect {
+            bounds: FloatRect::new(left, top, 50.0, 50.0),
+            z_level,
+        })
+    }
+
+    /// Draws the elements with [draw_layers], with a [Mark] for every element that is drawn
+    fn draw(camera: &Camera, elements: &mut Elements<'_>) -> RecordingTarget {
+        draw_with_drawables(camera, elements, &Vec::new())
+    }
+
+    fn draw_with_drawables<'s>(
+        camera: &Camera,
+        elements: &mut Elements<'s>,
+        drawables: &Drawables<'s>,
+    ) -> RecordingTarget {
+        let mut target = RecordingTarget::new();
+        draw_layers(
+            &mut &mut target,
+            camera,
+            elements,
+            drawables,
+            |target, _, _| target.draw(&Mark),
+        );
+        target
+    }
+
+    #[test]
+    fn only_world_elements_are_culled() {
+        let view_rect = FloatRect::new(0.0, 0.0, 800.0, 600.0);
+        assert!(should_draw(
+            &*rect(100.0, 100.0, DEFAULT_Z_LEVEL),
+            &view_rect
+        ));
+        assert!(!should_draw(
+            &*rect(2000.0, 100.0, DEFAULT_Z_LEVEL),
+            &view_rect
+        ));
+        // the HUD is drawn in screen coordinates, so the view of the camera does not matter
+        assert!(should_draw(&*rect(2000.0, 100.0, UI_Z_LEVEL), &view_rect));
+    }
+
+    #[test]
+    fn elements_outside_of_the_view_are_not_drawn() {
+        let camera = Camera::new((800.0, 600.0));
+        let mut elements: Elements = HashMap::new();
+        elements.insert(GElementID::new(), rect(100.0, 100.0, DEFAULT_Z_LEVEL));
+        elements.insert(GElementID::new(), rect(2000.0, 100.0, DEFAULT_Z_LEVEL));
+
+        assert_eq!(draw(&camera, &mut elements).draws.len(), 1);
+    }
+
+    #[test]
+    fn reticle_is_drawn_in_screen_coordinates_with_a_moved_camera() {
+        let mut camera = Camera::new((800.0, 600.0));
+        camera.set_center((1000.0, -500.0));
+        camera.set_zoom(2.0);
+
+        let mut elements: Elements = HashMap::new();
+        elements.insert(GElementID::new(), rect(990.0, -510.0, DEFAULT_Z_LEVEL));
+        elements.insert(
+            GElementID::new(),
+            Box::new(Reticle::new(ReticleShape::Cross).expect("could not create the reticle")),
+        );
+
+        // sorted by z-level, so the world comes first
+        let target = draw(&camera, &mut elements);
+        assert_eq!(
+            target.draws,
+            [
+                (Vector2f::new(1000.0, -500.0), Vector2f::new(400.0, 300.0)),
+                (Vector2f::new(400.0, 300.0), Vector2f::new(800.0, 600.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn drawables_are_drawn_between_the_elements_by_z_level() {
+        let mut camera = Camera::new((800.0, 600.0));
+        camera.set_center((1000.0, -500.0));
+        let world = (Vector2f::new(1000.0, -500.0), Vector2f::new(800.0, 600.0));
+        let screen = (Vector2f::new(400.0, 300.0), Vector2f::new(800.0, 600.0));
+
+        let mut elements: Elements = HashMap::new();
+        elements.insert(GElementID::new(), rect(990.0, -510.0, DEFAULT_Z_LEVEL));
+        elements.insert(GElementID::new(), rect(10.0, 10.0, UI_Z_LEVEL + 1));
+        let drawables: Drawables = vec![
+            (GElementID::new(), DEFAULT_Z_LEVEL - 1, Box::new(Mark)),
+            (GElementID::new(), UI_Z_LEVEL, Box::new(Mark)),
+        ];
+
+        // drawn again every time, they are not used up
+        for _ in 0..2 {
+            let target = draw_with_drawables(&camera, &mut elements, &drawables);
+            assert_eq!(target.draws, [world, world, screen, screen]);
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use sfml::{
+    graphics::{Color, CustomShape, Drawable, Font, RenderTarget, Shape, Text, Transformable},
+    system::{Vector2f, Vector2i},
+    window::{Event, Key},
+};
+
+use crate::shapes::RectRoundShape;
+
+use super::{FOCUS_OUTLINE_COLOR, NativeElement};
+
+/// Space between the box and the label
+const LABEL_GAP: f32 = 12.0;
+
+/// A box that flips between checked and unchecked on every click
+pub struct CheckBox<'s> {
+    pub shape: CustomShape<'s>,
+    label: Option<Text<'s>>,
+    size: f32,
+    is_hovered: bool,
+    is_pressed: bool,
+    is_focused: bool,
+    checked: bool,
+}
+
+impl<'s> CheckBox<'s> {
+    pub fn new(size: f32) -> Self {
+        let mut shape = RectRoundShape::new(size, size, size / 5.0);
+        shape.set_outline_thickness(3.0);
+
+        let mut checkbox = CheckBox {
+            shape,
+            label: None,
+            size,
+            is_hovered: false,
+            is_pressed: false,
+            is_focused: false,
+            checked: false,
+        };
+        checkbox.update_appearance();
+        checkbox
+    }
+
+    pub fn with_label(mut self, text_str: &str, font: &'s Font, size: u32) -> Self {
+        let mut text = Text::new(text_str, font, size);
+        text.set_fill_color(Color::WHITE);
+
+        // Center the label vertically next to the box
+        let text_rect = text.local_bounds();
+        text.set_origin((0.0, text_rect.top + text_rect.height / 2.0));
+
+        self.label = Some(text);
+        self.update_label_position();
+        self
+    }
+
+    pub fn with_checked(mut self, checked: bool) -> Self {
+        self.set_checked(checked);
+        self
+    }
+
+    pub fn is_checked(&self) -> bool {
+        self.checked
+    }
+
+    pub fn set_checked(&mut self, checked: bool) {
+        self.checked = checked;
+        self.update_appearance();
+    }
+
+    // Returns true if the checked state was toggled (pressed and released on the element)
+    pub fn update(&mut self, event: &Event, mouse_pos: impl Into<Vector2f>) -> bool {
+        let contains = self.contains_point(mouse_pos.into());
+        let old_hovered = self.is_hovered;
+        let old_pressed = self.is_pressed;
+        let mut toggled = false;
+
+        match event {
+            Event::MouseMoved { .. } => {
+                self.is_hovered = contains;
+                if self.is_pressed && !contains {
+                    self.is_pressed = false;
+                }
+            }
+            Event::MouseButtonPressed { .. } => {
+                if contains {
+                    self.is_pressed = true;
+                }
+            }
+            Event::MouseButtonReleased { .. } => {
+                if self.is_pressed && contains {
+                    self.checked = !self.checked;
+                    toggled = true;
+                }
+                self.is_pressed = false;
+            }
+            Event::KeyPressed {
+                code: Key::Enter | Key::Space,
+                ..
+            } => {
+                if self.is_focused {
+                    self.checked = !self.checked;
+                    toggled = true;
+                }
+            }
+            _ => {}
+        }
+
+        if toggled || old_hovered != self.is_hovered || old_pressed != self.is_pressed {
+            self.update_appearance();
+        }
+
+        toggled
+    }
+
+    fn update_appearance(&mut self) {
+        let outline = if self.is_focused {
+            FOCUS_OUTLINE_COLOR
+        } else if self.is_pressed {
+            Color::rgb(180, 180, 180)
+        } else if self.is_hovered {
+            Color::rgb(160, 160, 160)
+        } else {
+            Color::rgb(120, 120, 120)
+        };
+        self.shape.set_outline_color(outline);
+
+        // checked boxes are filled, unchecked ones only have an outline
+        if self.checked {
+            self.shape.set_fill_color(Color::rgb(80, 140, 220));
+        } else {
+            self.shape.set_fill_color(Color::TRANSPARENT);
+        }
+    }
+
+    fn update_label_position(&mut self) {
+        if let Some(text) = &mut self.label {
+            let position = self.shape.position();
+            text.set_position((
+                position.x + self.size + LABEL_GAP,
+                position.y + self.size / 2.0,
+            ));
+        }
+    }
+}
+
+impl Drawable for CheckBox<'_> {
+    fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
+        &'a self,
+        target: &mut dyn RenderTarget,
+        states: &sfml::graphics::RenderStates<'texture, 'shader, 'shader_texture>,
+    ) {
+        self.shape.draw(target, states);
+        if let Some(label) = self.label.as_ref() {
+            label.draw(target, states);
+        }
+    }
+}
+
+impl<'s> NativeElement<'s> for CheckBox<'s> {
+    fn set_position(&mut self, position: Vector2f) {
+        self.shape.set_position(position);
+        self.update_label_position();
+    }
+
+    fn position(&self) -> Vector2f {
+        self.shape.position()
+    }
+
+    fn size(&self) -> Vector2f {
+        match &self.label {
+            Some(label) => {
+                let label_size = label.local_bounds().size();
+                Vector2f::new(
+                    self.size + LABEL_GAP + label_size.x,
+                    self.size.max(label_size.y),
+                )
+            }
+            None => Vector2f::new(self.size, self.size),
+        }
+    }
+
+    fn contains_point(&self, point: Vector2f) -> bool {
+        // only the box is clickable, not the label
+        self.shape.global_bounds().contains(point)
+    }
+
+    fn handle_event(&mut self, event: &Event, mouse_pos: Vector2i) -> bool {
+        self.update(event, mouse_pos.as_other::<f32>())
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.is_focused = focused;
+        self.update_appearance();
+    }
+
+    fn is_focused(&self) -> bool {
+        self.is_focused
+    }
+}
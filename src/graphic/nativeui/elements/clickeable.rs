@@ -1,18 +1,20 @@
 use sfml::{
     graphics::{Color, CustomShape, Drawable, Font, RenderTarget, Shape, Text, Transformable},
     system::{Vector2f, Vector2i},
-    window::Event,
+    window::{Event, Key},
 };
 
 use crate::shapes::RectRoundShape;
 
-use super::NativeElement;
+use super::{FOCUS_OUTLINE_COLOR, NativeElement};
 
 pub struct Clickable<'s> {
     pub shape: CustomShape<'s>,
     text: Option<Text<'s>>,
     is_hovered: bool,
     is_pressed: bool,
+    is_focused: bool,
+    on_click: Option<Box<dyn FnMut() + 's>>,
 }
 
 impl<'s> Clickable<'s> {
@@ -29,6 +31,8 @@ impl<'s> Clickable<'s> {
             text: None,
             is_hovered: false,
             is_pressed: false,
+            is_focused: false,
+            on_click: None,
         }
     }
 
@@ -50,9 +54,18 @@ impl<'s> Clickable<'s> {
         self
     }
 
+    /// Calls `f` every time the element is clicked
+    ///
+    /// The callback runs on the main thread while the event is processed, in [Self::update] or
+    /// [NativeElement::handle_event]. It replaces a callback that was set before.
+    pub fn on_click(mut self, f: impl FnMut() + 's) -> Self {
+        self.on_click = Some(Box::new(f));
+        self
+    }
+
     // Returns true if clicked (pressed and released on the element)
     pub fn update(&mut self, event: &Event, mouse_pos: impl Into<Vector2f>) -> bool {
-        let contains = self.contains_point(mouse_pos);
+        let contains = self.contains_point(mouse_pos.into());
         let old_hovered = self.is_hovered;
         let old_pressed = self.is_pressed;
         let mut clicked = false;
@@ -75,9 +88,21 @@ impl<'s> Clickable<'s> {
                 }
                 self.is_pressed = false;
             }
+            Event::KeyPressed {
+                code: Key::Enter | Key::Space,
+                ..
+            } => {
+                if self.is_focused {
+                    clicked = true;
+                }
+            }
             _ => {}
         }
 
+        if clicked && let Some(on_click) = self.on_click.as_mut() {
+            on_click();
+        }
+
         // Update visual appearance if state changed
         if old_hovered != self.is_hovered || old_pressed != self.is_pressed {
             self.update_appearance();
@@ -97,6 +122,9 @@ impl<'s> Clickable<'s> {
             self.shape.set_fill_color(Color::rgb(80, 80, 80));
             self.shape.set_outline_color(Color::rgb(120, 120, 120));
         }
+        if self.is_focused {
+            self.shape.set_outline_color(FOCUS_OUTLINE_COLOR);
+        }
     }
 
     pub fn draw(&self, target: &mut dyn RenderTarget) {
@@ -121,7 +149,7 @@ impl Drawable for Clickable<'_> {
 }
 
 impl<'s> NativeElement<'s> for Clickable<'s> {
-    fn set_position(&mut self, position: impl Into<Vector2f>) {
+    fn set_position(&mut self, position: Vector2f) {
         self.shape.set_position(position);
 
         // Update text position if it exists
@@ -138,9 +166,12 @@ impl<'s> NativeElement<'s> for Clickable<'s> {
         self.shape.position()
     }
 
-    fn contains_point(&self, point: impl Into<Vector2f>) -> bool {
+    fn size(&self) -> Vector2f {
+        self.shape.global_bounds().size()
+    }
+
+    fn contains_point(&self, point: Vector2f) -> bool {
         let bounds = self.shape.global_bounds();
-        let point: Vector2f = point.into();
 
         point.x >= bounds.left
             && point.x <= bounds.left + bounds.width
@@ -149,6 +180,15 @@ impl<'s> NativeElement<'s> for Clickable<'s> {
     }
 
     fn handle_event(&mut self, event: &Event, mouse_pos: Vector2i) -> bool {
-        true
+        self.update(event, mouse_pos.as_other::<f32>())
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.is_focused = focused;
+        self.update_appearance();
+    }
+
+    fn is_focused(&self) -> bool {
+        self.is_focused
     }
 }
@@ -0,0 +1,143 @@
+use sfml::SfResult;
+use sfml::cpp::FBox;
+use sfml::graphics::{
+    Color, CustomShapePoints, Drawable, PrimitiveType, RenderStates, RenderTarget, Vertex,
+    VertexBuffer, VertexBufferUsage,
+};
+use sfml::system::{Vector2f, Vector2i};
+use sfml::window::Event;
+use tracing::error;
+
+use crate::shapes::RectRoundShape;
+
+use super::NativeElement;
+
+/// Points on each corner of the outline, also used when the corners are not rounded, so that
+/// the amount of vertices never changes
+const CORNER_POINTS: usize = 8;
+/// the center, the outline and the first outline point again to close the fan
+const VERTEX_COUNT: usize = CORNER_POINTS * 4 + 2;
+
+/// A rectangle that fades from one color at the top to another at the bottom
+///
+/// SFML shapes only have a single fill color, so this is drawn from its own [VertexBuffer].
+/// The corners can be rounded like a [RectRoundShape].
+pub struct GradientRect {
+    vertices: FBox<VertexBuffer>,
+    position: Vector2f,
+    size: Vector2f,
+    radius: f32,
+    top_color: Color,
+    bottom_color: Color,
+}
+
+impl GradientRect {
+    pub fn new(size: Vector2f, top_color: Color, bottom_color: Color) -> SfResult<Self> {
+        let mut rect = GradientRect {
+            vertices: VertexBuffer::new(
+                PrimitiveType::TRIANGLE_FAN,
+                VERTEX_COUNT,
+                VertexBufferUsage::STATIC,
+            )?,
+            position: Vector2f::new(0.0, 0.0),
+            size,
+            radius: 0.0,
+            top_color,
+            bottom_color,
+        };
+        rect.rebuild();
+        Ok(rect)
+    }
+
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.set_radius(radius);
+        self
+    }
+
+    /// Rounds the corners, `0.0` gives sharp corners
+    pub fn set_radius(&mut self, radius: f32) {
+        self.radius = radius.max(0.0);
+        self.rebuild();
+    }
+
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    pub fn set_colors(&mut self, top_color: Color, bottom_color: Color) {
+        self.top_color = top_color;
+        self.bottom_color = bottom_color;
+        self.rebuild();
+    }
+
+    pub fn colors(&self) -> (Color, Color) {
+        (self.top_color, self.bottom_color)
+    }
+
+    /// Color at `y`, relative to the top of the rectangle
+    fn color_at(&self, y: f32) -> Color {
+        let amount = if self.size.y > 0.0 {
+            (y / self.size.y).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let lerp = |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * amount) as u8;
+        Color::rgba(
+            lerp(self.top_color.r, self.bottom_color.r),
+            lerp(self.top_color.g, self.bottom_color.g),
+            lerp(self.top_color.b, self.bottom_color.b),
+            lerp(self.top_color.a, self.bottom_color.a),
+        )
+    }
+
+    fn rebuild(&mut self) {
+        let outline = RectRoundShape::basic_shape(self.size.x, self.size.y, self.radius)
+            .with_corner_points(CORNER_POINTS);
+        let vertex =
+            |point: Vector2f| Vertex::with_pos_color(self.position + point, self.color_at(point.y));
+
+        let mut vertices = Vec::with_capacity(VERTEX_COUNT);
+        vertices.push(vertex(self.size / 2.0));
+        vertices.extend((0..outline.point_count()).map(|i| vertex(outline.point(i))));
+        vertices.push(vertex(outline.point(0)));
+
+        if let Err(e) = self.vertices.update(&vertices, 0) {
+            error!("could not update the vertices of a gradient rect: {e}");
+        }
+    }
+}
+
+impl Drawable for GradientRect {
+    fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
+        &'a self,
+        target: &mut dyn RenderTarget,
+        states: &RenderStates<'texture, 'shader, 'shader_texture>,
+    ) {
+        target.draw_with_renderstates(&*self.vertices, states);
+    }
+}
+
+impl<'s> NativeElement<'s> for GradientRect {
+    fn set_position(&mut self, position: Vector2f) {
+        self.position = position;
+        self.rebuild();
+    }
+
+    fn position(&self) -> Vector2f {
+        self.position
+    }
+
+    fn size(&self) -> Vector2f {
+        self.size
+    }
+
+    fn contains_point(&self, point: Vector2f) -> bool {
+        let relative = point - self.position;
+        (0.0..=self.size.x).contains(&relative.x) && (0.0..=self.size.y).contains(&relative.y)
+    }
+
+    /// A gradient only shows something, it never reacts to events
+    fn handle_event(&mut self, _event: &Event, _mouse_pos: Vector2i) -> bool {
+        false
+    }
+}
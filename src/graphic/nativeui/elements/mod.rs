@@ -1,12 +1,29 @@
-use sfml::graphics::Drawable;
+use sfml::graphics::{Color, Drawable};
 use sfml::system::{Vector2f, Vector2i};
 use sfml::window::Event;
 
+pub mod checkbox;
 pub mod clickeable;
+pub mod gradient;
+pub mod stack;
+pub mod textinput;
 
-pub trait NativeElement<'s>: Drawable {
-    fn set_position(&mut self, position: impl Into<Vector2f>);
+/// Outline color of elements that have the keyboard focus
+pub const FOCUS_OUTLINE_COLOR: Color = Color::rgb(230, 180, 60);
+
+pub trait NativeElement<'s>: Drawable + 's {
+    fn set_position(&mut self, position: Vector2f);
     fn position(&self) -> Vector2f;
-    fn contains_point(&self, point: impl Into<Vector2f>) -> bool;
+    /// The size of the whole element, used to arrange elements next to each other
+    fn size(&self) -> Vector2f;
+    fn contains_point(&self, point: Vector2f) -> bool;
     fn handle_event(&mut self, event: &Event, mouse_pos: Vector2i) -> bool;
+
+    /// Focused elements can be activated with the keyboard, elements that do not support this
+    /// can ignore it
+    #[allow(unused_variables)]
+    fn set_focused(&mut self, focused: bool) {}
+    fn is_focused(&self) -> bool {
+        false
+    }
 }
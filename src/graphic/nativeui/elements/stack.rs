@@ -0,0 +1,255 @@
+use sfml::{
+    graphics::{Drawable, RenderTarget},
+    system::{Vector2f, Vector2i},
+    window::Event,
+};
+
+use super::NativeElement;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum StackDirection {
+    /// children are placed left to right
+    Horizontal,
+    /// children are placed top to bottom
+    #[default]
+    Vertical,
+}
+
+/// Arranges its children in a row or column, starting at its own position
+///
+/// Stacks are native elements themselves, so they can be nested.
+pub struct Stack<'s> {
+    children: Vec<Box<dyn NativeElement<'s>>>,
+    direction: StackDirection,
+    spacing: f32,
+    position: Vector2f,
+}
+
+impl<'s> Stack<'s> {
+    pub fn new(direction: StackDirection, spacing: f32) -> Self {
+        Stack {
+            children: Vec::new(),
+            direction,
+            spacing,
+            position: Vector2f::new(0.0, 0.0),
+        }
+    }
+
+    /// A stack that places its children left to right
+    pub fn horizontal(spacing: f32) -> Self {
+        Self::new(StackDirection::Horizontal, spacing)
+    }
+
+    /// A stack that places its children top to bottom
+    pub fn vertical(spacing: f32) -> Self {
+        Self::new(StackDirection::Vertical, spacing)
+    }
+
+    pub fn with(mut self, child: Box<dyn NativeElement<'s>>) -> Self {
+        self.push(child);
+        self
+    }
+
+    /// Adds a child after the last one
+    pub fn push(&mut self, child: Box<dyn NativeElement<'s>>) {
+        self.children.push(child);
+        self.reflow();
+    }
+
+    pub fn children(&self) -> &[Box<dyn NativeElement<'s>>] {
+        &self.children
+    }
+
+    /// If a child changes its size, call [Self::reflow] afterwards
+    pub fn children_mut(&mut self) -> &mut [Box<dyn NativeElement<'s>>] {
+        &mut self.children
+    }
+
+    pub fn len(&self) -> usize {
+        self.children.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    pub fn direction(&self) -> StackDirection {
+        self.direction
+    }
+
+    pub fn set_spacing(&mut self, spacing: f32) {
+        self.spacing = spacing;
+        self.reflow();
+    }
+
+    pub fn spacing(&self) -> f32 {
+        self.spacing
+    }
+
+    /// Places all children next to each other again
+    pub fn reflow(&mut self) {
+        let mut cursor = self.position;
+        for child in self.children.iter_mut() {
+            child.set_position(cursor);
+            let size = child.size();
+            match self.direction {
+                StackDirection::Horizontal => cursor.x += size.x + self.spacing,
+                StackDirection::Vertical => cursor.y += size.y + self.spacing,
+            }
+        }
+    }
+}
+
+impl Drawable for Stack<'_> {
+    fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
+        &'a self,
+        target: &mut dyn RenderTarget,
+        states: &sfml::graphics::RenderStates<'texture, 'shader, 'shader_texture>,
+    ) {
+        for child in self.children.iter() {
+            child.draw(target, states);
+        }
+    }
+}
+
+impl<'s> NativeElement<'s> for Stack<'s> {
+    fn set_position(&mut self, position: Vector2f) {
+        self.position = position;
+        self.reflow();
+    }
+
+    fn position(&self) -> Vector2f {
+        self.position
+    }
+
+    fn size(&self) -> Vector2f {
+        let gaps = self.spacing * self.children.len().saturating_sub(1) as f32;
+        let sizes = self.children.iter().map(|child| child.size());
+        match self.direction {
+            StackDirection::Horizontal => Vector2f::new(
+                sizes.clone().map(|s| s.x).sum::<f32>() + gaps,
+                sizes.map(|s| s.y).fold(0.0, f32::max),
+            ),
+            StackDirection::Vertical => Vector2f::new(
+                sizes.clone().map(|s| s.x).fold(0.0, f32::max),
+                sizes.map(|s| s.y).sum::<f32>() + gaps,
+            ),
+        }
+    }
+
+    fn contains_point(&self, point: Vector2f) -> bool {
+        self.children
+            .iter()
+            .any(|child| child.contains_point(point))
+    }
+
+    /// Forwards the event to all children, returns true if any of them was activated
+    fn handle_event(&mut self, event: &Event, mouse_pos: Vector2i) -> bool {
+        let mut activated = false;
+        for child in self.children.iter_mut() {
+            activated |= child.handle_event(event, mouse_pos);
+        }
+        activated
+    }
+
+    /// A stack has the focus when one of its children has it
+    fn is_focused(&self) -> bool {
+        self.children.iter().any(|child| child.is_focused())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An element with a fixed size that only remembers its position
+    struct Fixed {
+        size: Vector2f,
+        position: Vector2f,
+    }
+
+    impl Fixed {
+        fn boxed<'s>(width: f32, height: f32) -> Box<dyn NativeElement<'s>> {
+            Box::new(Fixed {
+                size: Vector2f::new(width, height),
+                position: Vector2f::new(0.0, 0.0),
+            })
+        }
+    }
+
+    impl Drawable for Fixed {
+        fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
+            &'a self,
+            _target: &mut dyn RenderTarget,
+            _states: &sfml::graphics::RenderStates<'texture, 'shader, 'shader_texture>,
+        ) {
+        }
+    }
+
+    impl NativeElement<'_> for Fixed {
+        fn set_position(&mut self, position: Vector2f) {
+            self.position = position;
+        }
+
+        fn position(&self) -> Vector2f {
+            self.position
+        }
+
+        fn size(&self) -> Vector2f {
+            self.size
+        }
+
+        fn contains_point(&self, _point: Vector2f) -> bool {
+            false
+        }
+
+        fn handle_event(&mut self, _event: &Event, _mouse_pos: Vector2i) -> bool {
+            false
+        }
+    }
+
+    fn positions(stack: &Stack) -> Vec<(f32, f32)> {
+        stack
+            .children()
+            .iter()
+            .map(|child| (child.position().x, child.position().y))
+            .collect()
+    }
+
+    fn three_children(stack: Stack<'_>) -> Stack<'_> {
+        stack
+            .with(Fixed::boxed(10.0, 20.0))
+            .with(Fixed::boxed(30.0, 5.0))
+            .with(Fixed::boxed(15.0, 15.0))
+    }
+
+    #[test]
+    fn vertical_stack_places_children_below_each_other() {
+        let mut stack = three_children(Stack::vertical(4.0));
+        stack.set_position(Vector2f::new(100.0, 50.0));
+
+        assert_eq!(
+            positions(&stack),
+            [(100.0, 50.0), (100.0, 74.0), (100.0, 83.0)]
+        );
+        assert_eq!(stack.size(), Vector2f::new(30.0, 48.0));
+    }
+
+    #[test]
+    fn horizontal_stack_places_children_next_to_each_other() {
+        let mut stack = three_children(Stack::horizontal(4.0));
+        stack.set_position(Vector2f::new(100.0, 50.0));
+
+        assert_eq!(
+            positions(&stack),
+            [(100.0, 50.0), (114.0, 50.0), (148.0, 50.0)]
+        );
+        assert_eq!(stack.size(), Vector2f::new(63.0, 20.0));
+
+        stack.set_spacing(0.0);
+        assert_eq!(
+            positions(&stack),
+            [(100.0, 50.0), (110.0, 50.0), (140.0, 50.0)]
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use sfml::{
+    graphics::{
+        Color, CustomShape, Drawable, Font, RectangleShape, RenderTarget, Shape, Text,
+        Transformable,
+    },
+    system::{Vector2f, Vector2i},
+    window::{Event, Key},
+};
+
+use crate::shapes::RectRoundShape;
+
+use super::{FOCUS_OUTLINE_COLOR, NativeElement};
+
+/// Space between the border and the text
+const PADDING: f32 = 10.0;
+const CARET_WIDTH: f32 = 2.0;
+
+/// A single line of editable text
+///
+/// Clicking on it focuses it, clicking anywhere else removes the focus. Only a focused text input
+/// takes text from the keyboard.
+pub struct TextInput<'s> {
+    pub shape: CustomShape<'s>,
+    text: Text<'s>,
+    caret: RectangleShape<'s>,
+    content: String,
+    is_focused: bool,
+}
+
+impl<'s> TextInput<'s> {
+    pub fn new(width: f32, font: &'s Font, size: u32) -> Self {
+        let height = size as f32 + PADDING * 2.0;
+        let mut shape = RectRoundShape::new(width, height, 6.0);
+        shape.set_fill_color(Color::rgb(40, 40, 40));
+
+        let mut text = Text::new("", font, size);
+        text.set_fill_color(Color::WHITE);
+
+        let mut caret = RectangleShape::with_size(Vector2f::new(CARET_WIDTH, size as f32));
+        caret.set_fill_color(Color::WHITE);
+
+        let mut input = TextInput {
+            shape,
+            text,
+            caret,
+            content: String::new(),
+            is_focused: false,
+        };
+        input.update_appearance();
+        input.update_text_position();
+        input
+    }
+
+    pub fn text(&self) -> &str {
+        &self.content
+    }
+
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.content = text.into();
+        self.update_text();
+    }
+
+    fn update_text(&mut self) {
+        self.text.set_string(self.content.as_str());
+        self.update_caret_position();
+    }
+
+    fn update_text_position(&mut self) {
+        let position = self.shape.position();
+        self.text
+            .set_position((position.x + PADDING, position.y + PADDING * 0.5));
+        self.update_caret_position();
+    }
+
+    fn update_caret_position(&mut self) {
+        // the caret is always at the end of the text
+        let end = self.text.find_character_pos(self.content.chars().count());
+        self.caret
+            .set_position((end.x, self.shape.position().y + PADDING));
+    }
+
+    fn update_appearance(&mut self) {
+        if self.is_focused {
+            self.shape.set_outline_color(FOCUS_OUTLINE_COLOR);
+        } else {
+            self.shape.set_outline_color(Color::rgb(120, 120, 120));
+        }
+    }
+}
+
+impl Drawable for TextInput<'_> {
+    fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
+        &'a self,
+        target: &mut dyn RenderTarget,
+        states: &sfml::graphics::RenderStates<'texture, 'shader, 'shader_texture>,
+    ) {
+        self.shape.draw(target, states);
+        self.text.draw(target, states);
+        if self.is_focused {
+            self.caret.draw(target, states);
+        }
+    }
+}
+
+impl<'s> NativeElement<'s> for TextInput<'s> {
+    fn set_position(&mut self, position: Vector2f) {
+        self.shape.set_position(position);
+        self.update_text_position();
+    }
+
+    fn position(&self) -> Vector2f {
+        self.shape.position()
+    }
+
+    fn size(&self) -> Vector2f {
+        self.shape.global_bounds().size()
+    }
+
+    fn contains_point(&self, point: Vector2f) -> bool {
+        self.shape.global_bounds().contains(point)
+    }
+
+    /// Returns true if the text was changed
+    fn handle_event(&mut self, event: &Event, mouse_pos: Vector2i) -> bool {
+        match *event {
+            Event::MouseButtonPressed { .. } => {
+                let contains = self.contains_point(mouse_pos.as_other::<f32>());
+                self.set_focused(contains);
+                false
+            }
+            // control characters like backspace or enter also get sent as text
+            Event::TextEntered { unicode } if self.is_focused && !unicode.is_control() => {
+                self.content.push(unicode);
+                self.update_text();
+                true
+            }
+            Event::KeyPressed {
+                code: Key::Backspace,
+                ..
+            } if self.is_focused => {
+                // pop removes a whole char, not just a byte
+                let changed = self.content.pop().is_some();
+                self.update_text();
+                changed
+            }
+            _ => false,
+        }
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.is_focused = focused;
+        self.update_appearance();
+    }
+
+    fn is_focused(&self) -> bool {
+        self.is_focused
+    }
+}
@@ -0,0 +1,37 @@
+//! Perspective math for starfields and other things that fly towards the camera
+//!
+//! Distances are measured from the camera, the near plane is where something has its actual
+//! size.
+
+use sfml::system::{Vector2f, Vector2u};
+
+/// How much something at `distance` is scaled on the screen, `1.0` at the near plane
+#[inline]
+pub fn scale(distance: f32, near: f32) -> f32 {
+    near / distance
+}
+
+/// Projects a world `position` at `distance` onto a screen of size `screen`
+///
+/// The world origin is in the center of the screen. Pixels are square, so x and y are scaled
+/// the same way, no matter the aspect ratio of the screen.
+#[inline]
+pub fn project(position: Vector2f, distance: f32, near: f32, screen: Vector2u) -> Vector2f {
+    let center = Vector2f::new(screen.x as f32 / 2.0, screen.y as f32 / 2.0);
+    position * scale(distance, near) + center
+}
+
+/// Where `distance` is between the near plane (`0.0`) and the far plane (`1.0`)
+///
+/// This is not clamped, things behind the near or far plane are outside of `0.0..=1.0`.
+#[inline]
+pub fn depth_ratio(distance: f32, near: f32, far: f32) -> f32 {
+    (distance - near) / (far - near)
+}
+
+/// Brightness of something at `distance`, `255` at the near plane and `0` at the far plane
+#[inline]
+pub fn brightness(distance: f32, near: f32, far: f32) -> u8 {
+    // the cast saturates, so this stays in range outside of the planes
+    ((1.0 - depth_ratio(distance, near, far)) * 255.0) as u8
+}
@@ -0,0 +1,56 @@
+//! Helpers to drive [ComprehensiveElements](ComprehensiveElement) without a window
+//!
+//! Only the update logic runs here, nothing is drawn. This is meant for tests, so it is only
+//! available with the `testing` feature.
+
+use std::collections::HashMap;
+
+use sfml::cpp::FBox;
+use sfml::graphics::Font;
+use sfml::window::VideoMode;
+
+use crate::counter::Counter;
+use crate::errors::BwgResult;
+
+use super::ComprehensiveElement;
+use super::elements::info::Info;
+
+/// fps limit of the headless [Counter], slow updates run every this many frames
+pub const HEADLESS_FPS_LIMIT: u64 = 60;
+
+/// Runs `try_update` of an element for `frames` frames, and `update_slow` every
+/// [HEADLESS_FPS_LIMIT] frames, like the main loop of a window would
+///
+/// `update_slow` also runs once after the last frame, so the returned custom info of the [Info]
+/// is up to date.
+pub fn run_headless<'s>(
+    element: &mut dyn ComprehensiveElement<'s>,
+    frames: u64,
+) -> BwgResult<HashMap<String, String>> {
+    // the info borrows the font for as long as the element lives, so it has to outlive the
+    // element. Leaking one small font per test run is fine.
+    let font: &'static FBox<Font> = Box::leak(Box::new(headless_font()?));
+    let mut counter = Counter::start(HEADLESS_FPS_LIMIT)?;
+    let mut info = Info::new(font, &VideoMode::new(1280, 720, 32), &counter);
+
+    for _ in 0..frames {
+        counter.frame_start();
+        element.try_update(&counter, &mut info)?;
+        if counter.frames % counter.fps_limit == 1 {
+            element.update_slow(&counter, &mut info);
+        }
+        counter.frame_prepare_display();
+    }
+    element.update_slow(&counter, &mut info);
+
+    Ok(info
+        .custom_info()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect())
+}
+
+fn headless_font() -> BwgResult<FBox<Font>> {
+    let mut font = Font::new()?;
+    font.load_from_memory_static(include_bytes!("../../resources/sansation.ttf"))?;
+    Ok(font)
+}
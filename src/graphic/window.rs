@@ -0,0 +1,25 @@
+use sfml::cpp::FBox;
+use sfml::graphics::RenderWindow;
+use sfml::window::{ContextSettings, Style, VideoMode};
+
+use crate::errors::BwgResult;
+
+/// Antialiasing level that smooths the edges of shapes without costing much
+pub const DEFAULT_MSAA: u32 = 4;
+
+/// Opens a [RenderWindow] with `msaa` samples of antialiasing
+///
+/// `0` disables antialiasing, which is what `&Default::default()` context settings do. The
+/// driver may use a lower level than requested if it does not support it.
+pub fn open_window(
+    video: VideoMode,
+    title: &str,
+    style: Style,
+    msaa: u32,
+) -> BwgResult<FBox<RenderWindow>> {
+    let settings = ContextSettings {
+        antialiasing_level: msaa,
+        ..Default::default()
+    };
+    Ok(RenderWindow::new(video, title, style, &settings)?)
+}
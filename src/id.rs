@@ -0,0 +1,31 @@
+use std::marker::PhantomData;
+
+/// Random identifier for elements of some kind
+///
+/// The marker `T` makes sure that ids of different kinds of elements can not be mixed up, for
+/// example [GElementID](crate::graphic::GElementID) and
+/// [PElementID](crate::physics::PElementID) are distinct types.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Hash, Default)]
+pub struct Id<T> {
+    inner: u128,
+    _marker: PhantomData<T>,
+}
+
+impl<T> rand::distr::Distribution<Id<T>> for rand::distr::StandardUniform {
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Id<T> {
+        Id {
+            inner: rng.random(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Id<T> {
+    pub fn new() -> Self {
+        rand::random()
+    }
+
+    pub fn as_u128(&self) -> u128 {
+        self.inner
+    }
+}
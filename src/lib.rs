@@ -1,4 +1,13 @@
+use std::fs::File;
+use std::path::Path;
+
+use anyhow::{Context, anyhow};
 use tracing::trace;
+use tracing_appender::non_blocking::WorkerGuard;
+
+use sfml::window::VideoMode;
+
+use crate::errors::BwgResult;
 
 pub use egui;
 pub use egui_sfml;
@@ -8,22 +17,71 @@ pub use tracing;
 pub mod counter;
 pub mod errors;
 pub mod graphic;
+pub mod id;
 pub mod physics;
 pub mod shapes;
 
-pub fn setup(verbose: bool) {
+fn max_level(verbose: bool) -> tracing::Level {
+    if verbose {
+        tracing::Level::TRACE
+    } else {
+        tracing::Level::INFO
+    }
+}
+
+/// Sets up logging to stderr
+///
+/// Only the first call sets up the logger, later calls do nothing. Returns `true` if the logger
+/// was set up by this call.
+pub fn setup(verbose: bool) -> bool {
     let subscriber = tracing_subscriber::fmt()
-        .with_max_level(if verbose {
-            tracing::Level::TRACE
-        } else {
-            tracing::Level::INFO
-        })
+        .with_max_level(max_level(verbose))
         .without_time()
         .with_file(false)
         .with_target(false)
         .with_writer(std::io::stderr)
         .finish();
     // use that subscriber to process traces emitted after this point
-    tracing::subscriber::set_global_default(subscriber).expect("could not setup logger");
+    if tracing::subscriber::set_global_default(subscriber).is_err() {
+        trace!("the logger was already set up");
+        return false;
+    }
+    trace!("set up the logger");
+    true
+}
+
+/// Like [setup], but logs into the file at `path` instead of stderr
+///
+/// The file is written from a background thread. Keep the returned guard alive for as long as
+/// you want to log, the remaining logs are flushed when it is dropped.
+pub fn setup_to_file(verbose: bool, path: &Path) -> BwgResult<WorkerGuard> {
+    let file = File::create(path)
+        .with_context(|| format!("could not create the log file {}", path.display()))?;
+    let (writer, guard) = tracing_appender::non_blocking(file);
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(max_level(verbose))
+        .with_file(false)
+        .with_target(false)
+        .with_ansi(false)
+        .with_writer(writer)
+        .finish();
+    tracing::subscriber::set_global_default(subscriber).context("could not setup logger")?;
     trace!("set up the logger");
+    Ok(guard)
+}
+
+/// The video mode to use for a fullscreen window
+///
+/// This is the best fullscreen mode if there is one, otherwise the mode of the desktop. Some
+/// setups, like virtual displays, report no fullscreen modes at all.
+pub fn best_video_mode() -> BwgResult<VideoMode> {
+    if let Some(mode) = VideoMode::fullscreen_modes().first() {
+        return Ok(*mode);
+    }
+    let desktop = VideoMode::desktop_mode();
+    if desktop.width == 0 || desktop.height == 0 {
+        return Err(anyhow!("there is neither a fullscreen nor a desktop video mode").into());
+    }
+    trace!("no fullscreen video modes, using the desktop mode");
+    Ok(desktop)
 }
@@ -2,30 +2,221 @@ use rapier2d::prelude::*;
 use sfml::system::Vector2f;
 
 use crate::graphic::ComprehensiveElement;
+use crate::id::Id;
 
 pub mod world;
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Hash, Default)]
-pub struct PElementID {
-    inner: u128,
+pub struct PhysicsMarker;
+
+pub type PElementID = Id<PhysicsMarker>;
+
+/// Shape of the collider of a [PhysicsElement]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColliderKind {
+    /// A box with the given half extents, so a shape with a width of `100.0` and a height of
+    /// `40.0` is `Cuboid((50.0, 20.0).into())`
+    Cuboid(Vector2f),
+    /// A circle with the given radius
+    Ball(f32),
 }
 
-impl rand::distr::Distribution<PElementID> for rand::distr::StandardUniform {
-    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> PElementID {
-        PElementID {
-            inner: rng.random(),
+impl ColliderKind {
+    pub fn builder(&self) -> ColliderBuilder {
+        match *self {
+            Self::Cuboid(half_extents) => ColliderBuilder::cuboid(half_extents.x, half_extents.y),
+            Self::Ball(radius) => ColliderBuilder::ball(radius),
         }
     }
 }
 
-impl PElementID {
-    pub fn new() -> Self {
-        rand::random()
-    }
-}
 pub trait PhysicsElement<'s>: ComprehensiveElement<'s> {
     fn init_rigid_body(&self) -> RigidBody;
-    fn init_collider(&self) -> Collider;
+    /// The shape of the collider. It is centered on the element position, which should be the
+    /// center of the shape.
+    ///
+    /// By default, this is a cuboid with the size of the [bounds](ComprehensiveElement::bounds),
+    /// or a cuboid of `1.0` by `1.0` for elements without bounds.
+    fn collider_kind(&self) -> ColliderKind {
+        let size = self
+            .bounds()
+            .map_or(Vector2f::new(1.0, 1.0), |bounds| bounds.size());
+        ColliderKind::Cuboid(size / 2.0)
+    }
+    /// How bouncy the element is, `0.0` does not bounce at all and `1.0` bounces back as high as
+    /// it came from
+    ///
+    /// Like [Self::friction], this is applied in [PhysicsWorld2D::add](world::PhysicsWorld2D::add)
+    /// and overwrites what [Self::init_collider] set.
+    fn restitution(&self) -> f32 {
+        0.0
+    }
+    /// How much the element sticks to others when sliding along them, `0.0` slides freely
+    fn friction(&self) -> f32 {
+        0.5
+    }
+    /// Builds the collider from [Self::collider_kind]. Override this to configure the collider
+    /// further, [Self::restitution] and [Self::friction] are still applied afterwards.
+    fn init_collider(&self) -> Collider {
+        self.collider_kind().builder().build()
+    }
+    /// Sensors do not push other elements away, they only report overlaps in
+    /// [PhysicsWorld2D::drain_collision_events](world::PhysicsWorld2D::drain_collision_events)
+    fn is_sensor(&self) -> bool {
+        false
+    }
     fn set_position(&mut self, position: Vector2f);
     fn get_position(&self) -> Vector2f;
+    /// Rotation of the rigid body in radians, around the center of the element.
+    ///
+    /// Implementors should set the origin of their shape to its center, so that the rotation
+    /// happens around the same point as in the physics world.
+    #[allow(unused_variables)]
+    fn set_rotation(&mut self, radians: f32) {}
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use sfml::graphics::FloatRect;
+
+    use super::world::PhysicsWorld2D;
+    use super::*;
+
+    /// A box without any graphics, so the physics can be tested without a window
+    pub(crate) struct TestBody {
+        position: Vector2f,
+        /// full width and height, like the size of a shape
+        size: Vector2f,
+        fixed: bool,
+        restitution: f32,
+    }
+
+    impl TestBody {
+        pub(crate) fn new(position: impl Into<Vector2f>, size: impl Into<Vector2f>) -> Self {
+            Self {
+                position: position.into(),
+                size: size.into(),
+                fixed: false,
+                restitution: 0.0,
+            }
+        }
+
+        /// A body that is not moved by the physics, like a floor
+        pub(crate) fn fixed(mut self) -> Self {
+            self.fixed = true;
+            self
+        }
+
+        pub(crate) fn with_restitution(mut self, restitution: f32) -> Self {
+            self.restitution = restitution;
+            self
+        }
+    }
+
+    impl ComprehensiveElement<'_> for TestBody {}
+
+    impl PhysicsElement<'_> for TestBody {
+        fn init_rigid_body(&self) -> RigidBody {
+            if self.fixed {
+                RigidBodyBuilder::fixed().build()
+            } else {
+                RigidBodyBuilder::dynamic().build()
+            }
+        }
+
+        fn collider_kind(&self) -> ColliderKind {
+            ColliderKind::Cuboid(self.size / 2.0)
+        }
+
+        fn restitution(&self) -> f32 {
+            self.restitution
+        }
+
+        fn set_position(&mut self, position: Vector2f) {
+            self.position = position;
+        }
+
+        fn get_position(&self) -> Vector2f {
+            self.position
+        }
+    }
+
+    /// Only implements what a [PhysicsElement] has to, to check the defaults
+    struct Bounded {
+        bounds: FloatRect,
+    }
+
+    impl ComprehensiveElement<'_> for Bounded {
+        fn bounds(&self) -> Option<FloatRect> {
+            Some(self.bounds)
+        }
+    }
+
+    impl PhysicsElement<'_> for Bounded {
+        fn init_rigid_body(&self) -> RigidBody {
+            RigidBodyBuilder::dynamic().build()
+        }
+
+        fn set_position(&mut self, _position: Vector2f) {}
+
+        fn get_position(&self) -> Vector2f {
+            self.bounds.position() + self.bounds.size() / 2.0
+        }
+    }
+
+    #[test]
+    fn default_collider_is_a_cuboid_of_the_bounds() {
+        let element = Bounded {
+            bounds: FloatRect::new(10.0, 10.0, 100.0, 40.0),
+        };
+        assert_eq!(
+            element.collider_kind(),
+            ColliderKind::Cuboid(Vector2f::new(50.0, 20.0))
+        );
+        let collider = element.init_collider();
+        let cuboid = collider.shape().as_cuboid().unwrap();
+        assert_eq!(cuboid.half_extents, vector![50.0, 20.0]);
+    }
+
+    /// How far a body rose again after it came down the furthest, over `steps` steps
+    fn bounce_height(world: &mut PhysicsWorld2D<'_>, id: &PElementID, steps: usize) -> f32 {
+        let mut lowest = f32::MIN;
+        let mut height: f32 = 0.0;
+        for _ in 0..steps {
+            world.step();
+            let (_, position, _) = world
+                .positions()
+                .into_iter()
+                .find(|(other, _, _)| other == id)
+                .unwrap();
+            // y points down
+            lowest = lowest.max(position.y);
+            height = height.max(lowest - position.y);
+        }
+        height
+    }
+
+    #[test]
+    fn restitution_makes_bodies_bounce_higher() {
+        let mut heights = Vec::new();
+        for restitution in [0.1, 0.9] {
+            let mut world = PhysicsWorld2D::build().unwrap();
+            world.set_gravity(Vector2f::new(0.0, 500.0));
+            world.add(Box::new(
+                TestBody::new((0.0, 200.0), (1000.0, 20.0))
+                    .fixed()
+                    .with_restitution(restitution),
+            ));
+            let id = world.add(Box::new(
+                TestBody::new((0.0, 0.0), (20.0, 20.0)).with_restitution(restitution),
+            ));
+            heights.push(bounce_height(&mut world, &id, 180));
+        }
+        assert!(
+            heights[1] > heights[0],
+            "the bouncy body bounced {}, the other one {}",
+            heights[1],
+            heights[0]
+        );
+    }
 }
@@ -1,16 +1,47 @@
 use std::collections::HashMap;
 
+use bitflags::bitflags;
+use rapier2d::crossbeam::channel::{Receiver, unbounded};
 use rapier2d::prelude::*;
+use sfml::graphics::{
+    CircleShape, Color, FloatRect, RectangleShape, RenderTarget, Shape as _, Transformable as _,
+};
 use sfml::system::Vector2f;
+use tracing::debug;
 
 use crate::counter::Counter;
-use crate::errors::BwgResult;
+use crate::errors::{BwgError, BwgResult};
 use crate::graphic::ComprehensiveElement;
+use crate::graphic::bus::EventBus;
 use crate::graphic::elements::info::Info;
 
 use super::{PElementID, PhysicsElement};
 
 pub const DEFAULT_GRAVITY: Vector<f32> = vector![0.0, 9.81];
+/// Most steps that are done in one update. If the frames take longer than this, the physics
+/// slow down instead of taking ever longer to catch up.
+pub const MAX_SUBSTEPS: u32 = 8;
+/// Range of [PhysicsWorld2D::set_time_scale], steps get unstable far outside of it
+pub const TIME_SCALE_RANGE: std::ops::RangeInclusive<f32> = 0.05..=4.0;
+/// Outline color of the colliders, if they are drawn
+pub const DEBUG_COLOR: Color = Color::CYAN;
+/// Outline color of the element bounds, if they are drawn
+pub const DEBUG_BOUNDS_COLOR: Color = Color::MAGENTA;
+/// Color of the contact points, if they are drawn
+pub const DEBUG_CONTACT_COLOR: Color = Color::YELLOW;
+
+bitflags! {
+    /// Layers of debug information that [PhysicsWorld2D] draws over its elements
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    pub struct DebugDrawFlags: u8 {
+        /// the [bounds](ComprehensiveElement::bounds) of the elements
+        const ELEMENT_BOUNDS = 1 << 0;
+        /// the colliders, as rapier sees them
+        const COLLIDER_BOUNDS = 1 << 1;
+        /// the points where colliders touch
+        const CONTACTS = 1 << 2;
+    }
+}
 
 pub struct PhysicsWorld2D<'s> {
     pub gravity: Vector<f32>,
@@ -26,29 +57,23 @@ pub struct PhysicsWorld2D<'s> {
     pub ccd_solver: CCDSolver,
     pub query_pipeline: QueryPipeline,
     pub physics_hooks: (),
-    pub event_handler: (),
+    pub event_handler: ChannelEventCollector,
 
-    elements: HashMap<PElementID, (ColliderHandle, Box<dyn PhysicsElement<'s>>)>,
+    elements: HashMap<PElementID, (ColliderHandle, RigidBodyHandle, Box<dyn PhysicsElement<'s>>)>,
+    /// reverse lookup for [Self::elements], to find out which element a collider belongs to
+    collider_ids: HashMap<ColliderHandle, PElementID>,
+    /// collision events from [Self::event_handler], see [Self::drain_collision_events]
+    collision_events: Receiver<CollisionEvent>,
+    /// time that passed but was not simulated yet, in seconds
+    accumulator: f32,
+    /// simulated seconds per real second
+    time_scale: f32,
+    /// debug information that is drawn over the elements
+    debug_draw: DebugDrawFlags,
 }
 
 impl<'s> PhysicsWorld2D<'s> {
     pub fn build() -> BwgResult<Self> {
-        let mut rigid_body_set = RigidBodySet::new();
-        let mut collider_set = ColliderSet::new();
-
-        /* Create the ground. */
-        let collider = ColliderBuilder::cuboid(100.0, 0.1).build();
-        collider_set.insert(collider);
-
-        /* Create the bouncing ball. */
-        let rigid_body = RigidBodyBuilder::dynamic()
-            .translation(vector![0.0, 10.0])
-            .build();
-        let collider = ColliderBuilder::ball(0.5).restitution(0.7).build();
-        let ball_body_handle = rigid_body_set.insert(rigid_body);
-        collider_set.insert_with_parent(collider, ball_body_handle, &mut rigid_body_set);
-
-        /* Create other structures necessary for the simulation. */
         let gravity = DEFAULT_GRAVITY;
         let integration_parameters = IntegrationParameters::default();
         let physics_pipeline = PhysicsPipeline::new();
@@ -60,12 +85,15 @@ impl<'s> PhysicsWorld2D<'s> {
         let ccd_solver = CCDSolver::new();
         let query_pipeline = QueryPipeline::new();
         let physics_hooks = ();
-        let event_handler = ();
+        let (collision_sender, collision_events) = unbounded();
+        // contact force events are not enabled on any collider, so nothing is sent here
+        let (contact_force_sender, _) = unbounded();
+        let event_handler = ChannelEventCollector::new(collision_sender, contact_force_sender);
 
         let rigid_body_set = RigidBodySet::new();
         let collider_set = ColliderSet::new();
-
         let elements = HashMap::new();
+        let collider_ids = HashMap::new();
 
         Ok(Self {
             gravity,
@@ -83,44 +111,380 @@ impl<'s> PhysicsWorld2D<'s> {
             rigid_body_set,
             collider_set,
             elements,
+            collider_ids,
+            collision_events,
+            accumulator: 0.0,
+            time_scale: 1.0,
+            debug_draw: DebugDrawFlags::empty(),
         })
     }
 
     pub fn add(&mut self, element: Box<dyn PhysicsElement<'s>>) -> PElementID {
         let id = self.get_new_element_id();
 
-        let rbody_h = self.rigid_body_set.insert(element.init_rigid_body());
-
-        let mut coll = element.init_collider();
+        // The collider is centered on its rigid body, so the body needs to be placed where the
+        // element is. Otherwise the collider would be offset from the body, and rotate around
+        // the wrong point.
         let pos = element.get_position();
-        coll.set_position(Isometry::new(vector![pos.x, pos.y], 0.0));
-        let coll_h = self
-            .collider_set
-            .insert_with_parent(coll, rbody_h, &mut self.rigid_body_set);
+        let mut rbody = element.init_rigid_body();
+        rbody.set_translation(vector![pos.x, pos.y], true);
+        let rbody_h = self.rigid_body_set.insert(rbody);
 
-        self.elements.insert(id, (coll_h, element));
+        let mut collider = element.init_collider();
+        collider.set_restitution(element.restitution());
+        collider.set_friction(element.friction());
+        collider.set_sensor(element.is_sensor());
+        collider.set_active_events(collider.active_events() | ActiveEvents::COLLISION_EVENTS);
+        let coll_h =
+            self.collider_set
+                .insert_with_parent(collider, rbody_h, &mut self.rigid_body_set);
+
+        self.elements.insert(id, (coll_h, rbody_h, element));
+        self.collider_ids.insert(coll_h, id);
         id
     }
 
     pub fn get(&self, id: &PElementID) -> Option<&dyn PhysicsElement<'s>> {
-        self.elements.get(id).map(|v| v.1.as_ref())
+        self.elements.get(id).map(|v| v.2.as_ref())
+    }
+
+    /// Like [Self::get], but with an error if there is no such element
+    pub fn try_get(&self, id: &PElementID) -> BwgResult<&dyn PhysicsElement<'s>> {
+        self.get(id).ok_or(BwgError::ElementNotFound(id.as_u128()))
+    }
+
+    /// Connects two elements, so that they can only rotate around a shared point
+    ///
+    /// The anchors are the positions of that point relative to the centers of `a` and `b`, in
+    /// the same units as the element positions. If they are apart, the joint pulls the elements
+    /// together until they meet, so an anchor away from the center makes a pendulum.
+    pub fn add_revolute_joint(
+        &mut self,
+        a: &PElementID,
+        b: &PElementID,
+        anchor_a: Vector2f,
+        anchor_b: Vector2f,
+    ) -> BwgResult<()> {
+        let joint = RevoluteJointBuilder::new()
+            .local_anchor1(point![anchor_a.x, anchor_a.y])
+            .local_anchor2(point![anchor_b.x, anchor_b.y]);
+        self.add_joint(a, b, joint)
+    }
+
+    /// Connects two elements, so that they move and rotate together, like
+    /// [Self::add_revolute_joint] without the rotation
+    pub fn add_fixed_joint(
+        &mut self,
+        a: &PElementID,
+        b: &PElementID,
+        anchor_a: Vector2f,
+        anchor_b: Vector2f,
+    ) -> BwgResult<()> {
+        let joint = FixedJointBuilder::new()
+            .local_anchor1(point![anchor_a.x, anchor_a.y])
+            .local_anchor2(point![anchor_b.x, anchor_b.y]);
+        self.add_joint(a, b, joint)
+    }
+
+    fn add_joint(
+        &mut self,
+        a: &PElementID,
+        b: &PElementID,
+        joint: impl Into<GenericJoint>,
+    ) -> BwgResult<()> {
+        let body_a = self
+            .get_rigid_body_handle(a)
+            .ok_or(BwgError::ElementNotFound(a.as_u128()))?;
+        let body_b = self
+            .get_rigid_body_handle(b)
+            .ok_or(BwgError::ElementNotFound(b.as_u128()))?;
+        self.impulse_joint_set.insert(body_a, body_b, joint, true);
+        Ok(())
     }
 
     fn get_collider_handle(&self, id: &PElementID) -> Option<ColliderHandle> {
         self.elements.get(id).map(|v| v.0)
     }
 
+    fn get_rigid_body_handle(&self, id: &PElementID) -> Option<RigidBodyHandle> {
+        self.elements.get(id).map(|v| v.1)
+    }
+
+    /// Gets the rigid body of an element, if it can be moved by forces and impulses
+    fn get_movable_body_mut(&mut self, id: &PElementID) -> BwgResult<&mut RigidBody> {
+        let body_h = self
+            .get_rigid_body_handle(id)
+            .ok_or(BwgError::ElementNotFound(id.as_u128()))?;
+        let body = self
+            .rigid_body_set
+            .get_mut(body_h)
+            .ok_or_else(|| BwgError::Physics(format!("element {id:?} has no rigid body")))?;
+        if body.is_fixed() {
+            return Err(BwgError::Physics(format!(
+                "element {id:?} has a fixed rigid body, it can not be moved by forces"
+            )));
+        }
+        Ok(body)
+    }
+
+    /// Applies an instant impulse to the center of mass of the element
+    ///
+    /// Fails with [BwgError::Physics] if the element has a fixed rigid body.
+    pub fn apply_impulse(&mut self, id: &PElementID, impulse: Vector2f) -> BwgResult<()> {
+        self.get_movable_body_mut(id)?
+            .apply_impulse(vector![impulse.x, impulse.y], true);
+        Ok(())
+    }
+
+    /// Adds a force to the center of mass of the element
+    ///
+    /// Like with rapier, the force stays applied on every step until it is reset with
+    /// [RigidBody::reset_forces]. Fails with [BwgError::Physics] if the element has a fixed
+    /// rigid body.
+    pub fn apply_force(&mut self, id: &PElementID, force: Vector2f) -> BwgResult<()> {
+        self.get_movable_body_mut(id)?
+            .add_force(vector![force.x, force.y], true);
+        Ok(())
+    }
+
     pub fn get_mut(&mut self, id: &PElementID) -> Option<&mut dyn PhysicsElement<'s>> {
-        self.elements.get_mut(id).map(|v| v.1.as_mut())
+        self.elements.get_mut(id).map(|v| v.2.as_mut())
+    }
+
+    /// Like [Self::get_mut], but with an error if there is no such element
+    pub fn try_get_mut(&mut self, id: &PElementID) -> BwgResult<&mut dyn PhysicsElement<'s>> {
+        self.get_mut(id)
+            .ok_or(BwgError::ElementNotFound(id.as_u128()))
     }
 
     pub fn remove(&mut self, id: &PElementID) -> Option<Box<dyn PhysicsElement<'s>>> {
-        let (id, bo) = self.elements.remove(id)?;
-        self.collider_set
-            .remove(id, &mut self.island_manager, &mut self.rigid_body_set, true);
+        let (col_h, rbody_h, bo) = self.elements.remove(id)?;
+        self.collider_ids.remove(&col_h);
+        // this also removes the collider and the joints of the body
+        self.rigid_body_set.remove(
+            rbody_h,
+            &mut self.island_manager,
+            &mut self.collider_set,
+            &mut self.impulse_joint_set,
+            &mut self.multibody_joint_set,
+            true,
+        );
         Some(bo)
     }
 
+    /// Like [Self::remove], but with an error if there is no such element
+    pub fn try_remove(&mut self, id: &PElementID) -> BwgResult<Box<dyn PhysicsElement<'s>>> {
+        self.remove(id)
+            .ok_or(BwgError::ElementNotFound(id.as_u128()))
+    }
+
+    /// Sets the length of a simulation step in seconds, the default is `1/60`
+    ///
+    /// The world is stepped as often as needed to keep up with the real time, independent of the
+    /// frame rate. Non-positive values are ignored.
+    pub fn set_fixed_dt(&mut self, dt: f32) {
+        if dt <= 0.0 {
+            return;
+        }
+        self.integration_parameters.dt = dt;
+    }
+
+    pub fn fixed_dt(&self) -> f32 {
+        self.integration_parameters.dt
+    }
+
+    /// Slows the simulation down or speeds it up, `0.25` is slow motion, `2.0` twice as fast
+    ///
+    /// The world is still stepped once per [fixed dt](Self::fixed_dt) of real time, so the
+    /// accumulator and [MAX_SUBSTEPS] are not affected. Only the time that each step simulates is
+    /// multiplied by the scale. The scale is clamped to [TIME_SCALE_RANGE].
+    pub fn set_time_scale(&mut self, scale: f32) {
+        self.time_scale = scale.clamp(*TIME_SCALE_RANGE.start(), *TIME_SCALE_RANGE.end());
+    }
+
+    pub fn time_scale(&self) -> f32 {
+        self.time_scale
+    }
+
+    pub(super) fn step(&mut self) {
+        let mut integration_parameters = self.integration_parameters;
+        integration_parameters.dt *= self.time_scale;
+        self.physics_pipeline.step(
+            &self.gravity,
+            &integration_parameters,
+            &mut self.island_manager,
+            &mut self.broad_phase,
+            &mut self.narrow_phase,
+            &mut self.rigid_body_set,
+            &mut self.collider_set,
+            &mut self.impulse_joint_set,
+            &mut self.multibody_joint_set,
+            &mut self.ccd_solver,
+            Some(&mut self.query_pipeline),
+            &self.physics_hooks,
+            &self.event_handler,
+        );
+    }
+
+    /// Draw debug information over the elements, nothing is drawn by default
+    pub fn set_debug_draw(&mut self, debug_draw: DebugDrawFlags) {
+        self.debug_draw = debug_draw;
+    }
+
+    pub fn debug_draw(&self) -> DebugDrawFlags {
+        self.debug_draw
+    }
+
+    fn draw_debug(&self, target: &mut dyn RenderTarget) {
+        if self.debug_draw.contains(DebugDrawFlags::ELEMENT_BOUNDS) {
+            self.draw_element_bounds(target);
+        }
+        if self.debug_draw.contains(DebugDrawFlags::COLLIDER_BOUNDS) {
+            self.draw_colliders(target);
+        }
+        if self.debug_draw.contains(DebugDrawFlags::CONTACTS) {
+            self.draw_contacts(target);
+        }
+    }
+
+    /// Draws a dot where colliders touch, as of the last step
+    fn draw_contacts(&self, target: &mut dyn RenderTarget) {
+        const RADIUS: f32 = 3.0;
+        let mut dot = CircleShape::new(RADIUS, 12);
+        dot.set_origin((RADIUS, RADIUS));
+        dot.set_fill_color(DEBUG_CONTACT_COLOR);
+
+        for pair in self.narrow_phase.contact_pairs() {
+            if !pair.has_any_active_contact {
+                continue;
+            }
+            for manifold in &pair.manifolds {
+                // the solver contacts are already in world coordinates, which are the same as
+                // the element coordinates
+                for contact in &manifold.data.solver_contacts {
+                    dot.set_position((contact.point.x, contact.point.y));
+                    target.draw(&dot);
+                }
+            }
+        }
+    }
+
+    fn draw_element_bounds(&self, target: &mut dyn RenderTarget) {
+        for (_col_h, _rbody_h, element) in self.elements.values() {
+            let Some(bounds) = element.bounds() else {
+                continue;
+            };
+            let mut rect = RectangleShape::from_rect(bounds);
+            rect.set_fill_color(Color::TRANSPARENT);
+            rect.set_outline_color(DEBUG_BOUNDS_COLOR);
+            rect.set_outline_thickness(1.0);
+            target.draw(&rect);
+        }
+    }
+
+    fn draw_colliders(&self, target: &mut dyn RenderTarget) {
+        for (_handle, collider) in self.collider_set.iter() {
+            let pos = collider.position();
+            let center = Vector2f::new(pos.translation.x, pos.translation.y);
+            let rotation = pos.rotation.angle().to_degrees();
+            let shape = collider.shape();
+
+            if let Some(cuboid) = shape.as_cuboid() {
+                let half = Vector2f::new(cuboid.half_extents.x, cuboid.half_extents.y);
+                let mut rect = RectangleShape::with_size(half * 2.0);
+                rect.set_origin(half);
+                rect.set_position(center);
+                rect.set_rotation(rotation);
+                rect.set_fill_color(Color::TRANSPARENT);
+                rect.set_outline_color(DEBUG_COLOR);
+                rect.set_outline_thickness(1.0);
+                target.draw(&rect);
+            } else if let Some(ball) = shape.as_ball() {
+                let mut circle = CircleShape::new(ball.radius, 32);
+                circle.set_origin((ball.radius, ball.radius));
+                circle.set_position(center);
+                circle.set_rotation(rotation);
+                circle.set_fill_color(Color::TRANSPARENT);
+                circle.set_outline_color(DEBUG_COLOR);
+                circle.set_outline_thickness(1.0);
+                target.draw(&circle);
+            }
+        }
+    }
+
+    /// Sets the gravity, which is used from the next step on
+    pub fn set_gravity(&mut self, gravity: Vector2f) {
+        self.gravity = vector![gravity.x, gravity.y];
+    }
+
+    pub fn gravity(&self) -> Vector2f {
+        Vector2f::new(self.gravity.x, self.gravity.y)
+    }
+
+    /// Casts a ray from `origin` in the direction of `dir`, up to a distance of `max_toi`.
+    ///
+    /// Returns the first element that was hit and the point where it was hit. The query pipeline
+    /// is updated when the world is stepped, so elements show up after the first update.
+    pub fn raycast(
+        &self,
+        origin: Vector2f,
+        dir: Vector2f,
+        max_toi: f32,
+    ) -> Option<(PElementID, Vector2f)> {
+        let dir = vector![dir.x, dir.y].try_normalize(f32::EPSILON)?;
+        let ray = Ray::new(point![origin.x, origin.y], dir);
+        let (col_h, toi) = self.query_pipeline.cast_ray(
+            &self.rigid_body_set,
+            &self.collider_set,
+            &ray,
+            max_toi,
+            true,
+            QueryFilter::default(),
+        )?;
+        let id = self.collider_ids.get(&col_h)?;
+        let hit = ray.point_at(toi);
+        Some((*id, Vector2f::new(hit.x, hit.y)))
+    }
+
+    /// Finds the elements whose colliders touch `rect`, for example to select them with a box
+    ///
+    /// This compares the bounding boxes of the colliders, so an element can be found when only
+    /// the corner of its bounding box is in `rect`. Like with [Self::raycast], elements show up
+    /// after the first update.
+    pub fn query_rect(&self, rect: FloatRect) -> Vec<PElementID> {
+        let aabb = Aabb::new(
+            point![rect.left, rect.top],
+            point![rect.left + rect.width, rect.top + rect.height],
+        );
+        let mut ids = Vec::new();
+        self.query_pipeline
+            .colliders_with_aabb_intersecting_aabb(&aabb, |col_h| {
+                if let Some(id) = self.collider_ids.get(col_h) {
+                    ids.push(*id);
+                }
+                // keep searching
+                true
+            });
+        ids
+    }
+
+    /// Pairs of elements that started touching since the last call, or started to overlap if one
+    /// of them is a [sensor](PhysicsElement::is_sensor)
+    ///
+    /// Call this every frame, the events pile up otherwise. Pairs with an element that was
+    /// removed in the meantime are left out.
+    pub fn drain_collision_events(&mut self) -> Vec<(PElementID, PElementID)> {
+        self.collision_events
+            .try_iter()
+            .filter(|event| event.started())
+            .filter_map(|event| {
+                let a = self.collider_ids.get(&event.collider1())?;
+                let b = self.collider_ids.get(&event.collider2())?;
+                Some((*a, *b))
+            })
+            .collect()
+    }
+
     fn get_position(&self, id: &PElementID) -> Option<Vector2f> {
         let col_h = self.get_collider_handle(id)?;
         let elem = &self.collider_set[col_h];
@@ -128,6 +492,24 @@ impl<'s> PhysicsWorld2D<'s> {
         Some(Vector2f::from((pos.translation.x, pos.translation.y)))
     }
 
+    /// Position and rotation in radians of every element, as the physics see them
+    ///
+    /// These are the values the elements get on the next update, in the same units as their
+    /// positions. The order is arbitrary.
+    pub fn positions(&self) -> Vec<(PElementID, Vector2f, f32)> {
+        self.elements
+            .iter()
+            .map(|(id, (col_h, _rbody_h, _element))| {
+                let pos = self.collider_set[*col_h].position();
+                (
+                    *id,
+                    Vector2f::from((pos.translation.x, pos.translation.y)),
+                    pos.rotation.angle(),
+                )
+            })
+            .collect()
+    }
+
     pub fn get_new_element_id(&self) -> PElementID {
         let mut id: PElementID;
         let mut guard = 0;
@@ -149,32 +531,26 @@ impl<'s> PhysicsWorld2D<'s> {
 }
 
 impl<'s> ComprehensiveElement<'s> for PhysicsWorld2D<'s> {
-    fn update(&mut self, _counters: &Counter, _info: &mut Info<'s>) {
-        self.physics_pipeline.step(
-            &self.gravity,
-            &self.integration_parameters,
-            &mut self.island_manager,
-            &mut self.broad_phase,
-            &mut self.narrow_phase,
-            &mut self.rigid_body_set,
-            &mut self.collider_set,
-            &mut self.impulse_joint_set,
-            &mut self.multibody_joint_set,
-            &mut self.ccd_solver,
-            Some(&mut self.query_pipeline),
-            &self.physics_hooks,
-            &self.event_handler,
-        );
+    fn update(&mut self, counters: &Counter, _info: &mut Info<'s>) {
+        let dt = self.integration_parameters.dt;
+        self.accumulator += counters.dt;
+        let mut steps = 0;
+        while self.accumulator >= dt && steps < MAX_SUBSTEPS {
+            self.step();
+            self.accumulator -= dt;
+            steps += 1;
+        }
+        if self.accumulator >= dt {
+            debug!("physics can not keep up, dropping {:.3}s", self.accumulator);
+            self.accumulator = 0.0;
+        }
 
-        for (col_h, element) in self.elements.values_mut() {
-            let pos = {
-                let elem: &Collider = &self.collider_set[*col_h];
-                let pos = elem.position();
-                Some(Vector2f::from((pos.translation.x, pos.translation.y)))
-            }
-            .unwrap();
+        for (col_h, _rbody_h, element) in self.elements.values_mut() {
+            let elem: &Collider = &self.collider_set[*col_h];
+            let pos = elem.position();
 
-            element.set_position(pos);
+            element.set_position(Vector2f::from((pos.translation.x, pos.translation.y)));
+            element.set_rotation(pos.rotation.angle());
         }
     }
     fn draw_with(
@@ -184,8 +560,149 @@ impl<'s> ComprehensiveElement<'s> for PhysicsWorld2D<'s> {
         counters: &Counter,
         info: &mut Info<'s>,
     ) {
-        for (_colh, element) in self.elements.values_mut() {
+        for (_colh, _rbody_h, element) in self.elements.values_mut() {
             element.draw_with(sfml_w, egui_w, counters, info);
         }
+        self.draw_debug(&mut **sfml_w);
+    }
+
+    fn draw_to(&mut self, target: &mut dyn RenderTarget, counters: &Counter, info: &mut Info<'s>) {
+        for (_colh, _rbody_h, element) in self.elements.values_mut() {
+            element.draw_to(target, counters, info);
+        }
+        self.draw_debug(target);
+    }
+
+    fn egui_controls(&mut self, ui: &mut egui::Ui, _bus: &mut EventBus) {
+        let mut time_scale = self.time_scale;
+        if ui
+            .add(egui::Slider::new(&mut time_scale, TIME_SCALE_RANGE).text("time scale"))
+            .changed()
+        {
+            self.set_time_scale(time_scale);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::tests::TestBody;
+
+    #[test]
+    fn collider_has_the_half_extents_of_the_shape() {
+        let mut world = PhysicsWorld2D::build().unwrap();
+        let id = world.add(Box::new(TestBody::new((300.0, 200.0), (100.0, 40.0))));
+
+        let (col_h, _rbody_h, _element) = &world.elements[&id];
+        let collider = &world.collider_set[*col_h];
+        // positions are not scaled, so the collider has the size of the shape
+        let cuboid = collider.shape().as_cuboid().unwrap();
+        assert_eq!(cuboid.half_extents, vector![50.0, 20.0]);
+        assert_eq!(collider.translation(), &vector![300.0, 200.0]);
+    }
+
+    #[test]
+    fn remove_drops_the_rigid_bodies() {
+        let mut world = PhysicsWorld2D::build().unwrap();
+        let ids: Vec<_> = (0..100)
+            .map(|i| {
+                world.add(Box::new(TestBody::new(
+                    (i as f32 * 30.0, 0.0),
+                    (20.0, 20.0),
+                )))
+            })
+            .collect();
+        assert_eq!(world.rigid_body_set.len(), 100);
+        assert_eq!(world.collider_set.len(), 100);
+
+        for id in &ids {
+            assert!(world.remove(id).is_some());
+        }
+        assert_eq!(world.rigid_body_set.len(), 0);
+        assert_eq!(world.collider_set.len(), 0);
+    }
+
+    #[test]
+    fn forces_on_fixed_bodies_are_physics_errors() {
+        let mut world = PhysicsWorld2D::build().unwrap();
+        let fixed = world.add(Box::new(TestBody::new((0.0, 0.0), (20.0, 20.0)).fixed()));
+        let dynamic = world.add(Box::new(TestBody::new((100.0, 0.0), (20.0, 20.0))));
+        let push = Vector2f::new(10.0, 0.0);
+
+        assert!(matches!(
+            world.apply_impulse(&fixed, push),
+            Err(BwgError::Physics(_))
+        ));
+        assert!(matches!(
+            world.apply_force(&fixed, push),
+            Err(BwgError::Physics(_))
+        ));
+        assert!(world.apply_impulse(&dynamic, push).is_ok());
+        assert!(world.apply_force(&dynamic, push).is_ok());
+
+        world.remove(&dynamic);
+        assert!(matches!(
+            world.apply_impulse(&dynamic, push),
+            Err(BwgError::ElementNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn query_rect_finds_the_elements_inside() {
+        let mut world = PhysicsWorld2D::build().unwrap();
+        let body = |x, y| Box::new(TestBody::new((x, y), (20.0, 20.0)));
+        let inside_a = world.add(body(100.0, 100.0));
+        let inside_b = world.add(body(250.0, 150.0));
+        let outside = world.add(body(600.0, 100.0));
+        // the query pipeline is only updated by a step
+        world.step();
+
+        let mut found = world.query_rect(FloatRect::new(50.0, 50.0, 300.0, 200.0));
+        found.sort();
+        let mut expected = vec![inside_a, inside_b];
+        expected.sort();
+        assert_eq!(found, expected);
+        assert!(!found.contains(&outside));
+    }
+
+    /// Overrides [PhysicsElement::init_collider], but still wants its own material
+    struct CustomCollider;
+
+    impl ComprehensiveElement<'_> for CustomCollider {}
+
+    impl PhysicsElement<'_> for CustomCollider {
+        fn init_rigid_body(&self) -> RigidBody {
+            RigidBodyBuilder::dynamic().build()
+        }
+
+        fn init_collider(&self) -> Collider {
+            ColliderBuilder::ball(5.0).density(2.0).build()
+        }
+
+        fn restitution(&self) -> f32 {
+            0.9
+        }
+
+        fn friction(&self) -> f32 {
+            0.1
+        }
+
+        fn set_position(&mut self, _position: Vector2f) {}
+
+        fn get_position(&self) -> Vector2f {
+            Vector2f::new(0.0, 0.0)
+        }
+    }
+
+    #[test]
+    fn material_is_applied_to_custom_colliders() {
+        let mut world = PhysicsWorld2D::build().unwrap();
+        let id = world.add(Box::new(CustomCollider));
+        let (col_h, _rbody_h, _element) = &world.elements[&id];
+        let collider = &world.collider_set[*col_h];
+        assert_eq!(collider.restitution(), 0.9);
+        assert_eq!(collider.friction(), 0.1);
+        assert_eq!(collider.density(), 2.0);
     }
 }
@@ -3,7 +3,13 @@ use sfml::{
     system::Vector2f,
 };
 
+pub mod arc;
+pub mod ellipse;
+pub mod polygon;
 pub mod rectround;
+pub use arc::ArcShape;
+pub use ellipse::EllipseShape;
+pub use polygon::RegularPolygonShape;
 pub use rectround::RectRoundShape;
 
 #[derive(Clone, Copy)]
@@ -24,20 +30,58 @@ impl CustomShapePoints for TriangleShape {
     }
 }
 
+/// Cycles through all hues with full saturation and value, once every 6 units of `t`
 pub fn hue_time(t: f32) -> Color {
+    hsv_time(t, 1.0, 1.0)
+}
+
+/// Like [hue_time], but with a custom `saturation` and `value`, both in `0.0..=1.0`
+///
+/// A lower saturation gives pastel colors, a lower value gives darker colors.
+pub fn hsv_time(t: f32, saturation: f32, value: f32) -> Color {
     const fn lerp(from: f32, to: f32, amount: f32) -> f32 {
         from + amount * (to - from)
     }
 
+    let saturation = saturation.clamp(0.0, 1.0);
+    let value = value.clamp(0.0, 1.0);
+    // chroma, the strongest channel, and the minimum all channels share
+    let c = 255.0 * value * saturation;
+    let m = 255.0 * value - c;
+
     let frac = t.fract();
 
     let [r, g, b] = match (t % 6.0).floor() {
-        0.0 => [255., lerp(0., 255., frac), 0.],
-        1.0 => [lerp(255., 0., frac), 255., 0.],
-        2.0 => [0., 255., lerp(0., 255., frac)],
-        3.0 => [0., lerp(255., 0., frac), 255.],
-        4.0 => [lerp(0., 255., frac), 0., 255.],
-        _ => [255., 0., lerp(255., 0., frac)],
+        0.0 => [c, lerp(0., c, frac), 0.],
+        1.0 => [lerp(c, 0., frac), c, 0.],
+        2.0 => [0., c, lerp(0., c, frac)],
+        3.0 => [0., lerp(c, 0., frac), c],
+        4.0 => [lerp(0., c, frac), 0., c],
+        _ => [c, 0., lerp(c, 0., frac)],
     };
-    Color::rgb(r as u8, g as u8, b as u8)
+    Color::rgb((r + m) as u8, (g + m) as u8, (b + m) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hsv_time_with_full_saturation_and_value_is_hue_time() {
+        // the colors hue_time gave before it was based on hsv_time
+        let expected = [
+            (0.0, Color::rgb(255, 0, 0)),
+            (0.5, Color::rgb(255, 127, 0)),
+            (1.5, Color::rgb(127, 255, 0)),
+            (2.25, Color::rgb(0, 255, 63)),
+            (3.5, Color::rgb(0, 127, 255)),
+            (4.5, Color::rgb(127, 0, 255)),
+            (5.5, Color::rgb(255, 0, 127)),
+            (6.0, Color::rgb(255, 0, 0)),
+        ];
+        for (t, color) in expected {
+            assert_eq!(hsv_time(t, 1.0, 1.0), color, "t = {t}");
+            assert_eq!(hue_time(t), color, "t = {t}");
+        }
+    }
 }
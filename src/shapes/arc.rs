@@ -0,0 +1,99 @@
+use sfml::graphics::CustomShape;
+use sfml::{graphics::CustomShapePoints, system::Vector2f};
+
+/// A pie slice of a circle, going clockwise from `start_angle` to `end_angle`
+///
+/// Angles are in radians, `0.0` points to the right. Like with a
+/// [CircleShape](sfml::graphics::CircleShape), the center of the circle is at `(radius, radius)`.
+#[derive(Clone, Debug)]
+pub struct ArcShape {
+    radius: f32,
+    start_angle: f32,
+    end_angle: f32,
+    segments: usize,
+}
+
+impl ArcShape {
+    pub fn new<'s>(
+        radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+        segments: usize,
+    ) -> CustomShape<'s> {
+        CustomShape::new(Box::new(Self::basic_shape(
+            radius,
+            start_angle,
+            end_angle,
+            segments,
+        )))
+    }
+
+    pub fn basic_shape(radius: f32, start_angle: f32, end_angle: f32, segments: usize) -> Self {
+        ArcShape {
+            radius,
+            start_angle,
+            end_angle,
+            segments: segments.max(1),
+        }
+    }
+}
+
+impl CustomShapePoints for ArcShape {
+    fn point_count(&self) -> usize {
+        // the center, and both ends of every segment
+        self.segments + 2
+    }
+
+    fn point(&self, index: usize) -> Vector2f {
+        if index >= self.point_count() {
+            panic!("Point index out of bounds: {}", index);
+        }
+
+        let center = Vector2f::new(self.radius, self.radius);
+        if index == 0 {
+            return center;
+        }
+
+        let amount = (index - 1) as f32 / self.segments as f32;
+        let angle = self.start_angle + amount * (self.end_angle - self.start_angle);
+
+        center + Vector2f::new(angle.cos(), angle.sin()) * self.radius
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use super::*;
+
+    fn assert_near(a: Vector2f, b: Vector2f) {
+        assert!((a - b).length_sq() < 1e-6, "{a:?} is not {b:?}");
+    }
+
+    #[test]
+    fn has_the_center_and_both_ends_of_every_segment() {
+        for segments in [1, 2, 16] {
+            let arc = ArcShape::basic_shape(10.0, 0.0, PI, segments);
+            assert_eq!(arc.point_count(), segments + 2);
+        }
+    }
+
+    #[test]
+    fn ends_are_at_the_start_and_end_angle() {
+        let radius = 10.0;
+        let (start, end) = (PI / 4.0, 3.0 * PI / 2.0);
+        let arc = ArcShape::basic_shape(radius, start, end, 12);
+        let center = Vector2f::new(radius, radius);
+
+        assert_near(arc.point(0), center);
+        assert_near(
+            arc.point(1),
+            center + Vector2f::new(start.cos(), start.sin()) * radius,
+        );
+        assert_near(
+            arc.point(arc.point_count() - 1),
+            center + Vector2f::new(end.cos(), end.sin()) * radius,
+        );
+    }
+}
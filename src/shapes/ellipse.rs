@@ -0,0 +1,47 @@
+use sfml::graphics::CustomShape;
+use sfml::{graphics::CustomShapePoints, system::Vector2f};
+use std::f32::consts::PI;
+
+/// An ellipse with the radius `rx` along the x axis and `ry` along the y axis
+///
+/// The center is at `(rx, ry)`, so the shape starts at `(0, 0)` like a
+/// [CircleShape](sfml::graphics::CircleShape).
+#[derive(Clone, Debug)]
+pub struct EllipseShape {
+    rx: f32,
+    ry: f32,
+    points: usize,
+}
+
+impl EllipseShape {
+    pub fn new<'s>(rx: f32, ry: f32, points: usize) -> CustomShape<'s> {
+        CustomShape::new(Box::new(Self::basic_shape(rx, ry, points)))
+    }
+
+    pub fn basic_shape(rx: f32, ry: f32, points: usize) -> Self {
+        EllipseShape {
+            rx,
+            ry,
+            points: points.max(3),
+        }
+    }
+}
+
+impl CustomShapePoints for EllipseShape {
+    fn point_count(&self) -> usize {
+        self.points
+    }
+
+    fn point(&self, index: usize) -> Vector2f {
+        if index >= self.point_count() {
+            panic!("Point index out of bounds: {}", index);
+        }
+
+        let angle = index as f32 * (2.0 * PI / self.points as f32);
+
+        Vector2f::new(
+            self.rx + self.rx * angle.cos(),
+            self.ry + self.ry * angle.sin(),
+        )
+    }
+}
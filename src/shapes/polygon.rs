@@ -0,0 +1,85 @@
+use sfml::graphics::CustomShape;
+use sfml::{graphics::CustomShapePoints, system::Vector2f};
+use std::f32::consts::PI;
+
+/// A regular polygon with `sides` corners on a circle, the first corner is at the top
+///
+/// Like with a [CircleShape](sfml::graphics::CircleShape), the center of the circle is at
+/// `(radius, radius)`.
+#[derive(Clone, Debug)]
+pub struct RegularPolygonShape {
+    sides: usize,
+    radius: f32,
+}
+
+impl RegularPolygonShape {
+    /// # Panics
+    ///
+    /// Panics if `sides` is less than 3.
+    pub fn new<'s>(sides: usize, radius: f32) -> CustomShape<'s> {
+        CustomShape::new(Box::new(Self::basic_shape(sides, radius)))
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `sides` is less than 3.
+    pub fn basic_shape(sides: usize, radius: f32) -> Self {
+        assert!(sides >= 3, "a polygon needs at least 3 sides, not {sides}");
+        RegularPolygonShape { sides, radius }
+    }
+}
+
+impl CustomShapePoints for RegularPolygonShape {
+    fn point_count(&self) -> usize {
+        self.sides
+    }
+
+    fn point(&self, index: usize) -> Vector2f {
+        if index >= self.point_count() {
+            panic!("Point index out of bounds: {}", index);
+        }
+
+        // start at the top, y points down
+        let angle = -PI / 2.0 + index as f32 * (2.0 * PI / self.sides as f32);
+
+        Vector2f::new(self.radius, self.radius)
+            + Vector2f::new(angle.cos(), angle.sin()) * self.radius
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hexagon_has_six_corners_60_degrees_apart() {
+        let radius = 10.0;
+        let hexagon = RegularPolygonShape::basic_shape(6, radius);
+        assert_eq!(hexagon.point_count(), 6);
+
+        let center = Vector2f::new(radius, radius);
+        let angles: Vec<f32> = (0..6)
+            .map(|i| {
+                let offset = hexagon.point(i) - center;
+                assert!((offset.length_sq().sqrt() - radius).abs() < 1e-4);
+                offset.y.atan2(offset.x)
+            })
+            .collect();
+        // the first corner is at the top, y points down
+        assert!((angles[0] + PI / 2.0).abs() < 1e-4);
+        for (i, pair) in angles.windows(2).enumerate() {
+            let step = (pair[1] - pair[0]).rem_euclid(2.0 * PI).to_degrees();
+            assert!(
+                (step - 60.0).abs() < 1e-3,
+                "corner {i} to {} is {step}°",
+                i + 1
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn needs_three_sides() {
+        RegularPolygonShape::basic_shape(2, 10.0);
+    }
+}
@@ -2,6 +2,13 @@ use sfml::graphics::{CustomShape, Shape};
 use sfml::{graphics::CustomShapePoints, system::Vector2f};
 use std::f32::consts::PI;
 
+/// Corner resolution of [RectRoundShape::new] and [RectRoundShape::basic_shape]
+pub const DEFAULT_CORNER_POINTS: usize = 8;
+/// A corner needs a start and an end point, the angle between its points is divided by one less
+/// than this
+const MIN_CORNER_POINTS: usize = 2;
+const _: () = assert!(DEFAULT_CORNER_POINTS >= MIN_CORNER_POINTS);
+
 #[derive(Clone, Debug)]
 pub struct RectRoundShape {
     width: f32,
@@ -12,16 +19,7 @@ pub struct RectRoundShape {
 
 impl RectRoundShape {
     pub fn new<'s>(width: f32, height: f32, radius: f32) -> CustomShape<'s> {
-        // Ensure radius isn't too large
-        let max_radius = width.min(height) / 2.0;
-        let radius = radius.min(max_radius);
-
-        let inner = RectRoundShape {
-            width,
-            height,
-            radius,
-            points_per_corner: 8, // Default corner resolution
-        };
+        let inner = Self::basic_shape(width, height, radius);
         let mut shape = CustomShape::new(Box::new(inner));
         shape.set_outline_thickness(3.0);
         shape
@@ -36,7 +34,7 @@ impl RectRoundShape {
             width,
             height,
             radius,
-            points_per_corner: 8, // Default corner resolution
+            points_per_corner: DEFAULT_CORNER_POINTS,
         }
     }
 
@@ -53,6 +51,11 @@ impl CustomShapePoints for RectRoundShape {
     }
 
     fn point(&self, index: usize) -> Vector2f {
+        debug_assert!(
+            self.points_per_corner >= MIN_CORNER_POINTS,
+            "a corner needs at least {MIN_CORNER_POINTS} points, got {}",
+            self.points_per_corner
+        );
         let total_points = self.point_count();
 
         if index >= total_points {
@@ -91,3 +94,34 @@ impl CustomShapePoints for RectRoundShape {
         Vector2f { x, y }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn low_corner_counts_give_finite_points() {
+        for points in [0, 1, 2] {
+            let shape = RectRoundShape::basic_shape(100.0, 40.0, 10.0).with_corner_points(points);
+            assert!(shape.point_count() >= MIN_CORNER_POINTS * 4);
+            for i in 0..shape.point_count() {
+                let p = shape.point(i);
+                assert!(
+                    p.x.is_finite() && p.y.is_finite(),
+                    "point {i} of {points} per corner is {p:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn points_stay_inside_of_the_rect() {
+        // the radius is clamped to half of the shorter side
+        let shape = RectRoundShape::basic_shape(100.0, 40.0, 80.0);
+        for i in 0..shape.point_count() {
+            let p = shape.point(i);
+            assert!((-0.001..=100.001).contains(&p.x), "{p:?}");
+            assert!((-0.001..=40.001).contains(&p.y), "{p:?}");
+        }
+    }
+}